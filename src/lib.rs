@@ -1,6 +1,15 @@
-use std::{fmt::Debug, iter::repeat_with, mem::MaybeUninit, ptr};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+    iter::repeat_with,
+    mem::MaybeUninit,
+    ops::{Bound, Range, RangeBounds},
+    ptr,
+};
 
-#[derive(Default)]
 pub struct Vec<T> {
     /// Contents of the vector.
     /// Not every element is initialized, so accessing this directly is unsafe.
@@ -9,6 +18,20 @@ pub struct Vec<T> {
     /// Length of the vector.
     /// Necessary because the contents of `items` are not all necessarily initialized.
     len: usize,
+
+    /// Smoothed prediction of how much the vector will grow by next.
+    /// Only used by `reserve_predicted`.
+    predicted_growth: usize,
+
+    /// Largest capacity the vector is allowed to grow to, if any.
+    /// Set by `set_max_capacity`.
+    max_capacity: Option<usize>,
+}
+
+impl<T> Default for Vec<T> {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
 }
 
 impl<T> Clone for Vec<T>
@@ -19,6 +42,8 @@ where
         Self {
             items: self.items.clone(),
             len: self.len,
+            predicted_growth: self.predicted_growth,
+            max_capacity: self.max_capacity,
         }
     }
 }
@@ -38,13 +63,215 @@ where
     }
 }
 
-impl<T> Vec<T>
-where
-    T: Clone,
-{
+/// An error from decoding LEB128 varints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarintError {
+    /// The input ended in the middle of a varint.
+    Truncated,
+    /// A varint encoded a value too large for a `u64`.
+    Overflow,
+}
+
+impl Display for VarintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "input ended in the middle of a varint"),
+            Self::Overflow => write!(f, "varint does not fit in a u64"),
+        }
+    }
+}
+
+impl Error for VarintError {}
+
+/// An error from scattering values into a vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScatterError {
+    /// There wasn't exactly one value for each index.
+    LengthMismatch { indices: usize, values: usize },
+    /// An index was past the end of the vector.
+    OutOfBounds { index: usize, len: usize },
+}
+
+impl Display for ScatterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LengthMismatch { indices, values } => {
+                write!(f, "got {indices} indices but {values} values")
+            }
+            Self::OutOfBounds { index, len } => {
+                write!(f, "index {index} is out of bounds for length {len}")
+            }
+        }
+    }
+}
+
+impl Error for ScatterError {}
+
+/// An error from applying an invalid permutation to a vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermError {
+    /// The permutation's length didn't match the vector's.
+    LengthMismatch { perm: usize, len: usize },
+    /// The permutation referred to an index past the end of the vector.
+    OutOfBounds { index: usize, len: usize },
+    /// The permutation referred to the same index more than once.
+    Duplicate { index: usize },
+}
+
+impl Display for PermError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LengthMismatch { perm, len } => {
+                write!(f, "permutation of length {perm} applied to length {len}")
+            }
+            Self::OutOfBounds { index, len } => {
+                write!(f, "index {index} is out of bounds for length {len}")
+            }
+            Self::Duplicate { index } => write!(f, "index {index} appears more than once"),
+        }
+    }
+}
+
+impl Error for PermError {}
+
+/// An error from parsing a length-prefixed frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// The input was too short to hold the 4-byte length prefix.
+    MissingLength { available: usize },
+    /// The input was too short to hold the payload its length prefix promised.
+    Truncated { expected: usize, available: usize },
+}
+
+impl Display for FrameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingLength { available } => {
+                write!(f, "expected a 4-byte length prefix, got {available} bytes")
+            }
+            Self::Truncated {
+                expected,
+                available,
+            } => write!(
+                f,
+                "expected a {expected}-byte payload, got {available} bytes"
+            ),
+        }
+    }
+}
+
+impl Error for FrameError {}
+
+/// Integers whose byte order can be reversed, for converting between endiannesses.
+pub trait SwapBytes: Copy {
+    /// Returns the integer with the order of its bytes reversed.
+    #[must_use]
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! impl_swap_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl SwapBytes for $t {
+                fn swap_bytes(self) -> Self {
+                    <$t>::swap_bytes(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_swap_bytes!(u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+
+/// Values that can estimate how many bytes they take up once serialized, so buffers can be sized ahead of time.
+pub trait SizeHint {
+    /// Returns the estimated size of the value once serialized, in bytes.
+    fn serialized_size(&self) -> usize;
+}
+
+macro_rules! impl_size_hint {
+    ($($t:ty),*) => {
+        $(
+            impl SizeHint for $t {
+                fn serialized_size(&self) -> usize {
+                    size_of::<$t>()
+                }
+            }
+        )*
+    };
+}
+
+impl_size_hint!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool);
+
+impl SizeHint for char {
+    fn serialized_size(&self) -> usize {
+        self.len_utf8()
+    }
+}
+
+impl SizeHint for str {
+    fn serialized_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl SizeHint for String {
+    fn serialized_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: SizeHint> SizeHint for [T] {
+    fn serialized_size(&self) -> usize {
+        self.iter().map(SizeHint::serialized_size).sum()
+    }
+}
+
+/// An error from swapping two ranges of a vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeSwapError {
+    /// The ranges had different lengths.
+    LengthMismatch { a: usize, b: usize },
+    /// The ranges overlapped.
+    Overlap,
+    /// A range was decreasing or extended past the end of the vector.
+    OutOfBounds { range: Range<usize>, len: usize },
+}
+
+impl Display for RangeSwapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LengthMismatch { a, b } => {
+                write!(f, "cannot swap ranges of lengths {a} and {b}")
+            }
+            Self::Overlap => write!(f, "cannot swap overlapping ranges"),
+            Self::OutOfBounds { range, len } => {
+                write!(f, "range {range:?} is out of bounds for length {len}")
+            }
+        }
+    }
+}
+
+impl Error for RangeSwapError {}
+
+impl<T> Vec<T> {
+    /// Creates an empty vector that can hold `capacity` items without reallocating.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: repeat_with(MaybeUninit::uninit).take(capacity).collect(),
+            len: 0,
+            predicted_growth: 0,
+            max_capacity: None,
+        }
+    }
+
     /// Returns the vector's items as a Box slice.
     #[must_use]
-    pub fn into_box_slice(self) -> Box<[T]> {
+    pub fn into_box_slice(self) -> Box<[T]>
+    where
+        T: Clone,
+    {
         self.as_slice().into()
     }
 
@@ -54,25 +281,57 @@ where
             .take(new_capacity)
             .collect();
 
-        // Iterator over the items in the vector.
-        // Move all of these into the new Box slice
-        for (i, e) in self.as_slice().iter().cloned().enumerate() {
-            empty_space[i] = MaybeUninit::new(e);
+        // Move all the items in the vector into the new Box slice.
+        // The old Box slice only holds `MaybeUninit`s, so dropping it won't drop the moved items.
+        unsafe {
+            ptr::copy_nonoverlapping(self.items.as_ptr(), empty_space.as_mut_ptr(), self.len);
         }
 
         // Swap the current box slice with the new, larger one.
         self.items = empty_space;
     }
 
+    /// Returns the capacity to grow to so that at least `required` items fit, without exceeding the ceiling.
+    /// # Panics
+    /// Panics if `required` is greater than the ceiling set by `set_max_capacity`.
+    fn growth_target(&self, required: usize) -> usize {
+        let target = required.next_power_of_two();
+        let Some(max) = self.max_capacity else {
+            return target;
+        };
+        assert!(
+            required <= max,
+            "required capacity (is {required}) should be <= max capacity (is {max})"
+        );
+        target.min(max)
+    }
+
+    /// Returns the most items the vector may hold under the ceiling set by `set_max_capacity`, if there is one.
+    /// This is the ceiling itself, or the current capacity if the buffer was already larger than it.
+    fn capacity_limit(&self) -> Option<usize> {
+        self.max_capacity.map(|max| max.max(self.cap()))
+    }
+
+    /// Makes room for `n` more items, which are about to be stored, reallocating if necessary.
+    /// Unlike `reserve`, this never settles for less room than was asked for.
     fn realloc_if_spare_cap_lt_n(&mut self, n: usize) {
         if self.spare_capacity() < n {
-            self.realloc_to_desired_cap(n.next_power_of_two());
+            self.realloc_to_desired_cap(self.growth_target(self.len + n));
         }
     }
 
+    /// Reserves capacity for at least `additional` more items, reallocating if necessary.
+    /// If a ceiling is set with `set_max_capacity`, this only reserves as much as the ceiling allows.
+    pub fn reserve(&mut self, additional: usize) {
+        let additional = self.capacity_limit().map_or(additional, |limit| {
+            additional.min(limit.saturating_sub(self.len))
+        });
+        self.realloc_if_spare_cap_lt_n(additional);
+    }
+
     /// Grows the size of vector to fit more items.
     fn realloc(&mut self) {
-        self.realloc_to_desired_cap((self.cap() + 1).next_power_of_two());
+        self.realloc_to_desired_cap(self.growth_target(self.cap() + 1));
     }
 
     /// Calls `self.reallocate()` if `self.len() >= self.capacity()`.
@@ -83,7 +342,7 @@ where
         let len_gte_cap = self.len >= self.cap();
         if len_gte_cap {
             self.realloc();
-            debug_assert!(self.len >= self.cap());
+            debug_assert!(self.len < self.cap());
         }
         len_gte_cap
     }
@@ -96,12 +355,10 @@ where
     /// Returns a mutable reference to the first uninitialized value in `self.items`.
     /// Mostly for implementing methods like `self.push`.
     /// - Reallocates if `self.length >= self.capacity`.    
-    fn first_uninit(&mut self) -> &mut T {
+    fn first_uninit(&mut self) -> &mut MaybeUninit<T> {
         let s = self.uninint_slice();
-        let e = s
-            .get_mut(0)
-            .expect("Expected at least one uninitialized element");
-        unsafe { &mut *ptr::from_mut::<MaybeUninit<T>>(e).cast::<T>() }
+        s.get_mut(0)
+            .expect("Expected at least one uninitialized element")
     }
 
     /// Returns the uninitialized portion of the vector.
@@ -114,7 +371,7 @@ where
 
     /// Pushes an item onto the end of the vector
     pub fn push(&mut self, x: T) {
-        *self.first_uninit() = x;
+        self.first_uninit().write(x);
         self.len += 1;
     }
 
@@ -138,9 +395,7 @@ where
             self.push(x);
         });
     }
-}
 
-impl<T> Vec<T> {
     /// Returns a slice of all the items in the vector.
     #[must_use]
     pub fn as_slice(&self) -> &[T] {
@@ -188,8 +443,8 @@ impl<T> Vec<T> {
     /// # Safety
     /// The spare capacity of the vector must be non-zero
     pub unsafe fn push_unchecked(&mut self, x: T) {
+        self.items.get_unchecked_mut(self.len).write(x);
         self.len += 1;
-        *self.last_unchecked_mut() = x;
     }
 
     /// Pushes all the items from an iterator into the vector.
@@ -229,7 +484,7 @@ impl<T> Vec<T> {
     }
 
     /// Removes and returns the last element in the vector
-    pub fn pop(&mut self) -> Option<&T> {
+    pub const fn pop(&mut self) -> Option<&T> {
         if self.is_empty() {
             return None;
         }
@@ -250,7 +505,6 @@ impl<T> Vec<T> {
     }
 
     /// Returns the number of items the vector is currently storing.
-
     #[must_use]
     pub const fn len(&self) -> usize {
         self.len
@@ -261,4 +515,4194 @@ impl<T> Vec<T> {
     pub const fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Reverses the order of the `chunk`-sized chunks in the vector, without reversing the items within each chunk.
+    /// A trailing partial chunk is left in place.
+    /// # Panics
+    /// Panics if `chunk` is zero.
+    pub fn reverse_chunks(&mut self, chunk: usize) {
+        assert!(chunk != 0, "chunk size must be non-zero");
+        let whole = self.len - self.len % chunk;
+        let slice = &mut self.as_slice_mut()[..whole];
+
+        // Reversing everything puts the chunks in the right order, but backwards internally.
+        slice.reverse();
+        slice.chunks_exact_mut(chunk).for_each(<[T]>::reverse);
+    }
+
+    /// Shortens the vector to `len` items, dropping the rest.
+    /// Does nothing if the vector is already no longer than `len`.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let tail = ptr::from_mut(&mut self.as_slice_mut()[len..]);
+        self.len = len;
+        unsafe { ptr::drop_in_place(tail) };
+    }
+
+    /// Drops every item in the vector, keeping its capacity.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keeps only the items that satisfy `f`, dropping the rest and preserving the order of those kept.
+    /// `f` is called on each item exactly once, front to back.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut kept = 0;
+        let slice = self.as_slice_mut();
+        for i in 0..slice.len() {
+            if f(&slice[i]) {
+                slice.swap(kept, i);
+                kept += 1;
+            }
+        }
+        self.truncate(kept);
+    }
+
+    /// Removes every item that is equal to an earlier item, keeping the first occurrence of each value.
+    /// Unlike a consecutive dedup, duplicates are removed wherever they are in the vector.
+    pub fn dedup_global(&mut self)
+    where
+        T: Hash + Eq + Clone,
+    {
+        let mut seen = HashSet::new();
+        self.retain(|x| seen.insert(x.clone()));
+    }
+
+    /// Removes every item whose key is equal to the key of an earlier item, keeping the first item with each key.
+    pub fn dedup_by_key_global<K: Hash + Eq, F: FnMut(&T) -> K>(&mut self, mut key_fn: F) {
+        let mut seen = HashSet::new();
+        self.retain(|x| seen.insert(key_fn(x)));
+    }
+
+    /// Splits the vector before the first item that doesn't satisfy `f`.
+    /// Every item in the first slice satisfies `f`, and the second slice holds the rest.
+    pub fn split_at_predicate<F: FnMut(&T) -> bool>(&self, mut f: F) -> (&[T], &[T]) {
+        let slice = self.as_slice();
+        let mid = slice.iter().position(|x| !f(x)).unwrap_or(slice.len());
+        slice.split_at(mid)
+    }
+
+    /// Merges the items of `other` into the vector, keeping it sorted.
+    /// Both vectors must already be sorted, and items from `self` come before equal items from `other`.
+    pub fn merge_sorted(&mut self, mut other: Self)
+    where
+        T: Ord,
+    {
+        let (len, other_len) = (self.len, other.len);
+        self.realloc_if_spare_cap_lt_n(other_len);
+
+        // Until the merge is done, both vectors only claim to be empty,
+        // so a panicking comparison leaks the items instead of leaving duplicates behind.
+        self.len = 0;
+        other.len = 0;
+        let dst = self.items.as_mut_ptr().cast::<T>();
+        let src = other.items.as_ptr().cast::<T>();
+        let (mut i, mut j) = (len, other_len);
+
+        // Merge from the back, so every item of `self` is moved before its slot gets overwritten.
+        while j > 0 {
+            unsafe {
+                if i > 0 && *dst.add(i - 1) > *src.add(j - 1) {
+                    ptr::copy_nonoverlapping(dst.add(i - 1), dst.add(i + j - 1), 1);
+                    i -= 1;
+                } else {
+                    ptr::copy_nonoverlapping(src.add(j - 1), dst.add(i + j - 1), 1);
+                    j -= 1;
+                }
+            }
+        }
+
+        // The items of `other` now belong to `self`.
+        self.len = len + other_len;
+    }
+
+    /// Returns how many times each distinct item appears in the vector.
+    #[must_use]
+    pub fn counts(&self) -> HashMap<T, usize>
+    where
+        T: Hash + Eq + Clone,
+    {
+        let mut counts = HashMap::new();
+        for x in self.as_slice() {
+            *counts.entry(x.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Clamps every item in the vector so that it lies within `[min, max]`.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn clamp_all(&mut self, min: T, max: T)
+    where
+        T: PartialOrd + Clone,
+    {
+        for x in self.as_slice_mut() {
+            if *x < min {
+                *x = min.clone();
+            } else if *x > max {
+                *x = max.clone();
+            }
+        }
+    }
+
+    /// Returns the length of the longest run of consecutive equal items, or 0 if the vector is empty.
+    #[must_use]
+    pub fn longest_run(&self) -> usize
+    where
+        T: PartialEq,
+    {
+        self.as_slice()
+            .chunk_by(PartialEq::eq)
+            .map(<[T]>::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Rotates the vector so that the first item satisfying `f` is at index 0.
+    /// Returns whether such an item was found; the vector is left unchanged if not.
+    pub fn rotate_to_first<F: FnMut(&T) -> bool>(&mut self, f: F) -> bool {
+        let Some(mid) = self.as_slice().iter().position(f) else {
+            return false;
+        };
+        self.as_slice_mut().rotate_left(mid);
+        true
+    }
+
+    /// Moves the items out of the vector, front to back.
+    fn into_items(self) -> impl Iterator<Item = T> {
+        let len = self.len;
+        self.items
+            .into_vec()
+            .into_iter()
+            .take(len)
+            .map(|x| unsafe { x.assume_init() })
+    }
+
+    /// Splits the vector into `n` vectors of nearly equal length, moving the items in order.
+    /// Earlier parts are one item longer than later ones when the length isn't divisible by `n`.
+    /// # Panics
+    /// Panics if `n` is zero.
+    #[must_use]
+    pub fn split_into(self, n: usize) -> std::vec::Vec<Self> {
+        assert!(n != 0, "cannot split into zero parts");
+        let (base, extra) = (self.len / n, self.len % n);
+        let mut items = self.into_items();
+        (0..n)
+            .map(|i| {
+                let size = base + usize::from(i < extra);
+                let mut part = Self::with_capacity(size);
+                part.extend(items.by_ref().take(size));
+                part
+            })
+            .collect()
+    }
+
+    /// Inserts an item at `index`, shifting every item after it one place to the right.
+    /// # Panics
+    /// Panics if `index > self.len()`.
+    pub fn insert(&mut self, index: usize, x: T) {
+        assert!(
+            index <= self.len,
+            "insertion index (is {index}) should be <= len (is {})",
+            self.len
+        );
+        self.realloc_if_len_gte_cap();
+        unsafe {
+            let p = self.items.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(1), self.len - index);
+            (*p).write(x);
+        }
+        self.len += 1;
+    }
+
+    /// Removes and returns the item at `index`, shifting every item after it one place to the left.
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(
+            index < self.len,
+            "removal index (is {index}) should be < len (is {})",
+            self.len
+        );
+        self.len -= 1;
+        unsafe {
+            let p = self.items.as_mut_ptr().add(index);
+            let x = (*p).assume_init_read();
+            ptr::copy(p.add(1), p, self.len - index);
+            x
+        }
+    }
+
+    /// Inserts `value` into the sorted vector at its sorted position, keeping at most `max_len` items.
+    /// If the vector is already full, the largest item is dropped to make room, unless `value` would be the largest itself.
+    /// Returns whether `value` was inserted; a `max_len` of zero clears the vector and inserts nothing.
+    pub fn insert_sorted_bounded(&mut self, value: T, max_len: usize) -> bool
+    where
+        T: Ord,
+    {
+        if max_len == 0 {
+            self.clear();
+            return false;
+        }
+        if self.len >= max_len {
+            match self.last() {
+                Some(largest) if value < *largest => self.truncate(max_len - 1),
+                _ => return false,
+            }
+        }
+        let index = self.as_slice().partition_point(|x| *x <= value);
+        self.insert(index, value);
+        true
+    }
+
+    /// Returns a new vector holding `f` applied to each pair of items from `self` and `other`.
+    /// The result is as long as the shorter of the two vectors.
+    pub fn zip_map<U, V, F: FnMut(&T, &U) -> V>(&self, other: &Vec<U>, mut f: F) -> Vec<V> {
+        let pairs = self.as_slice().iter().zip(other.as_slice());
+        let mut mapped = Vec::with_capacity(pairs.len());
+        mapped.extend(pairs.map(|(a, b)| f(a, b)));
+        mapped
+    }
+
+    /// Removes every leading and trailing item equal to `value`, moving the rest to the front.
+    pub fn trim_matches(&mut self, value: &T)
+    where
+        T: PartialEq,
+    {
+        let slice = self.as_slice();
+        let end = slice.iter().rposition(|x| x != value).map_or(0, |i| i + 1);
+        let start = slice[..end].iter().take_while(|x| *x == value).count();
+        self.truncate(end);
+
+        // Rotating the leading matches to the back lets `truncate` drop them.
+        self.as_slice_mut().rotate_left(start);
+        self.truncate(end - start);
+    }
+
+    /// Iterates over the items in the vector along with their indices, counting up from `start` instead of 0.
+    pub fn iter_enumerated_from(&self, start: usize) -> impl Iterator<Item = (usize, &T)> {
+        self.as_slice()
+            .iter()
+            .enumerate()
+            .map(move |(i, x)| (start + i, x))
+    }
+
+    /// Returns the length of the longest prefix shared by the vector and `other`.
+    #[must_use]
+    pub fn common_prefix_len(&self, other: &[T]) -> usize
+    where
+        T: PartialEq,
+    {
+        self.as_slice()
+            .iter()
+            .zip(other)
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+
+    /// Pushes items from `iter` for as long as they satisfy `pred`.
+    /// The first item that fails `pred` is discarded, and nothing after it is pulled from `iter`.
+    pub fn extend_while<I: Iterator<Item = T>, F: FnMut(&T) -> bool>(
+        &mut self,
+        iter: I,
+        mut pred: F,
+    ) {
+        for x in iter {
+            if !pred(&x) {
+                break;
+            }
+            self.push(x);
+        }
+    }
+
+    /// Sets the length of the vector without initializing or dropping any items.
+    /// # Safety
+    /// - `new_len` must be <= `self.cap()`.
+    /// - The items at `self.len()..new_len` must be initialized.
+    pub const unsafe fn set_len(&mut self, new_len: usize) {
+        self.len = new_len;
+    }
+
+    /// Reserves space for `additional` more items and returns a pointer to the start of the spare capacity,
+    /// so that foreign code can write items into it directly.
+    /// Once the items are written, call `set_len` to make them part of the vector.
+    /// # Safety
+    /// - At most `additional` items may be written through the returned pointer.
+    /// - The pointer is invalidated by anything that may reallocate the vector, like `push` or `reserve`.
+    /// - Only the items that were actually initialized may be committed with `set_len`.
+    /// # Panics
+    /// Panics if the ceiling set by `set_max_capacity` doesn't leave room for `additional` more items.
+    pub unsafe fn reserve_for_ffi(&mut self, additional: usize) -> *mut T {
+        self.realloc_if_spare_cap_lt_n(additional);
+        self.items.as_mut_ptr().add(self.len).cast()
+    }
+
+    /// Consumes the vector and leaks its buffer, returning a mutable slice of its items that lives for as long as needed,
+    /// including `'static`.
+    /// The vector is shrunk to fit first, so no spare capacity is leaked along with the items.
+    #[must_use]
+    pub fn leak<'a>(mut self) -> &'a mut [T] {
+        self.shrink_to_fit();
+        let items = Box::leak(self.items);
+        unsafe { &mut *(ptr::from_mut::<[MaybeUninit<T>]>(items) as *mut [T]) }
+    }
+
+    /// Whether every item in the vector is equal to the first one, which is trivially true for an empty vector.
+    #[must_use]
+    pub fn all_equal(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        match self.as_slice() {
+            [] => true,
+            [first, rest @ ..] => rest.iter().all(|x| x == first),
+        }
+    }
+
+    /// Returns the mean of every complete `window`-sized window of the vector, front to back.
+    /// # Panics
+    /// Panics if `window` is zero.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn moving_average(&self, window: usize) -> Vec<f64>
+    where
+        T: Into<f64> + Copy,
+    {
+        assert!(window != 0, "window size must be non-zero");
+        let slice = self.as_slice();
+        let mut averages = Vec::with_capacity((slice.len() + 1).saturating_sub(window));
+        let Some(first) = slice.get(..window) else {
+            return averages;
+        };
+
+        // Keep a running sum, so each step only adds the new item and removes the oldest one.
+        let mut sum: f64 = first.iter().map(|&x| x.into()).sum();
+        averages.push(sum / window as f64);
+        for (&old, &new) in slice.iter().zip(&slice[window..]) {
+            sum += new.into() - old.into();
+            averages.push(sum / window as f64);
+        }
+        averages
+    }
+
+    /// Iterates over contiguous `n`-item slices of the vector, where the last one may be shorter.
+    /// # Panics
+    /// Panics if `n` is zero.
+    pub fn split_every(&self, n: usize) -> impl Iterator<Item = &[T]> {
+        assert!(n != 0, "chunk size must be non-zero");
+        self.as_slice().chunks(n)
+    }
+
+    /// Returns the index of every item that satisfies `f`, in ascending order.
+    pub fn indices_where<F: FnMut(&T) -> bool>(&self, mut f: F) -> Vec<usize> {
+        let mut indices = Vec::default();
+        indices.extend_naive(
+            self.as_slice()
+                .iter()
+                .enumerate()
+                .filter_map(|(i, x)| f(x).then_some(i)),
+        );
+        indices
+    }
+
+    /// Returns a new vector holding a clone of the item at each of `indices`, in order.
+    /// Returns `None` if any index is out of bounds.
+    #[must_use]
+    pub fn gather(&self, indices: &[usize]) -> Option<Self>
+    where
+        T: Clone,
+    {
+        let mut gathered = Self::with_capacity(indices.len());
+        for &i in indices {
+            gathered.push(self.get(i)?.clone());
+        }
+        Some(gathered)
+    }
+
+    /// Writes a clone of each of `values` to the item at the matching index in `indices`.
+    /// Nothing is written unless every index is valid, and items that aren't indexed are left untouched.
+    /// # Errors
+    /// Returns an error if `indices` and `values` have different lengths, or if any index is out of bounds.
+    pub fn scatter(&mut self, indices: &[usize], values: &[T]) -> Result<(), ScatterError>
+    where
+        T: Clone,
+    {
+        if indices.len() != values.len() {
+            return Err(ScatterError::LengthMismatch {
+                indices: indices.len(),
+                values: values.len(),
+            });
+        }
+        if let Some(&index) = indices.iter().find(|&&i| i >= self.len) {
+            return Err(ScatterError::OutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+        let slice = self.as_slice_mut();
+        for (&i, x) in indices.iter().zip(values) {
+            slice[i] = x.clone();
+        }
+        Ok(())
+    }
+
+    /// Returns the dot product of the vector and `other`, or `None` if their lengths differ.
+    #[must_use]
+    pub fn dot(&self, other: &[T]) -> Option<T>
+    where
+        T: std::iter::Sum + std::ops::Mul<Output = T> + Copy,
+    {
+        let slice = self.as_slice();
+        (slice.len() == other.len()).then(|| slice.iter().zip(other).map(|(&a, &b)| a * b).sum())
+    }
+
+    /// Multiplies every item in the vector by `factor`.
+    pub fn scale(&mut self, factor: T)
+    where
+        T: std::ops::MulAssign + Copy,
+    {
+        for x in self.as_slice_mut() {
+            *x *= factor;
+        }
+    }
+
+    /// Returns a new vector where each item is the largest of the items up to and including that index.
+    #[must_use]
+    pub fn running_max(&self) -> Self
+    where
+        T: PartialOrd + Clone,
+    {
+        let mut maxima = Self::with_capacity(self.len);
+        for x in self.as_slice() {
+            let max = match maxima.last() {
+                Some(max) if max >= x => max.clone(),
+                _ => x.clone(),
+            };
+            maxima.push(max);
+        }
+        maxima
+    }
+
+    /// Keeps the longest prefix of the vector whose total weight is at most `max_weight`, dropping the rest.
+    pub fn truncate_by_weight<F: FnMut(&T) -> usize>(&mut self, max_weight: usize, mut weight: F) {
+        let mut total = 0usize;
+        let kept = self
+            .as_slice()
+            .iter()
+            .take_while(|x| {
+                total = total.saturating_add(weight(x));
+                total <= max_weight
+            })
+            .count();
+        self.truncate(kept);
+    }
+
+    /// Iterates over the items in the vector, along with whether each one is the last item.
+    pub fn iter_with_last(&self) -> impl Iterator<Item = (&T, bool)> {
+        let last = self.len.wrapping_sub(1);
+        self.as_slice()
+            .iter()
+            .enumerate()
+            .map(move |(i, x)| (x, i == last))
+    }
+
+    /// Creates a vector by calling `gen` and pushing each item it returns, until it returns `None`.
+    pub fn from_generator<F: FnMut() -> Option<T>>(mut gen: F) -> Self {
+        Self::unfold((), |()| gen())
+    }
+
+    /// Creates a vector by repeatedly calling `step` on a state that starts out as `initial`,
+    /// pushing each item it returns, until it returns `None`.
+    pub fn unfold<S, F: FnMut(&mut S) -> Option<T>>(initial: S, mut step: F) -> Self {
+        let mut state = initial;
+        let mut v = Self::default();
+        while let Some(x) = step(&mut state) {
+            v.push(x);
+        }
+        v
+    }
+
+    /// Returns the indices that would sort the vector, without modifying it.
+    /// The sort is stable, so equal items keep their relative order.
+    #[must_use]
+    pub fn argsort(&self) -> Vec<usize>
+    where
+        T: Ord,
+    {
+        let slice = self.as_slice();
+        let mut indices = Vec::with_capacity(slice.len());
+        indices.extend(0..slice.len());
+        indices
+            .as_slice_mut()
+            .sort_by(|&a, &b| slice[a].cmp(&slice[b]));
+        indices
+    }
+
+    /// Reorders the vector so that the item at each index `i` is the one that was at `perm[i]`.
+    /// # Errors
+    /// Returns an error, leaving the vector unchanged, if `perm` isn't a permutation of `0..self.len()`.
+    pub fn apply_permutation(&mut self, perm: &[usize]) -> Result<(), PermError> {
+        let len = self.len;
+        if perm.len() != len {
+            return Err(PermError::LengthMismatch {
+                perm: perm.len(),
+                len,
+            });
+        }
+        let mut seen = vec![false; len];
+        for &index in perm {
+            let seen = seen
+                .get_mut(index)
+                .ok_or(PermError::OutOfBounds { index, len })?;
+            if std::mem::replace(seen, true) {
+                return Err(PermError::Duplicate { index });
+            }
+        }
+
+        // Follow each cycle of the permutation, pulling each item into place with a swap.
+        // `seen` is reused to mark positions that have already been filled.
+        let slice = self.as_slice_mut();
+        for start in 0..len {
+            let mut i = start;
+            while seen[i] {
+                seen[i] = false;
+                let src = perm[i];
+                if src == start {
+                    break;
+                }
+                slice.swap(i, src);
+                i = src;
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a vector from the first `max` items of `iter`, ignoring the rest.
+    pub fn from_iter_capped<I: IntoIterator<Item = T>>(iter: I, max: usize) -> Self {
+        let mut v = Self::default();
+        v.extend(iter.into_iter().take(max));
+        v
+    }
+
+    /// Treats the vector as a row-major `rows` × `cols` matrix, and returns its transpose as a row-major `cols` × `rows` matrix.
+    /// Returns `None` if the vector doesn't hold exactly `rows * cols` items.
+    #[must_use]
+    pub fn transpose(&self, rows: usize, cols: usize) -> Option<Self>
+    where
+        T: Clone,
+    {
+        if rows.checked_mul(cols)? != self.len {
+            return None;
+        }
+        let slice = self.as_slice();
+        let mut transposed = Self::with_capacity(self.len);
+        transposed
+            .extend((0..cols).flat_map(|c| (0..rows).map(move |r| slice[r * cols + c].clone())));
+        Some(transposed)
+    }
+
+    /// Returns the lower median of the vector, or `None` if it's empty.
+    /// This runs a quickselect over the indices of the items, so the vector isn't sorted or modified.
+    #[must_use]
+    pub fn median(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.select_nth(self.len.checked_sub(1)? / 2)
+    }
+
+    /// Returns the `k`-th smallest item in the vector (counting from 0), or `None` if `k >= self.len()`.
+    /// This runs a quickselect over the indices of the items, so the vector isn't sorted or modified.
+    #[must_use]
+    pub fn select_nth(&self, k: usize) -> Option<&T>
+    where
+        T: Ord,
+    {
+        let slice = self.as_slice();
+        if k >= slice.len() {
+            return None;
+        }
+        let mut indices: Box<[usize]> = (0..slice.len()).collect();
+        let (_, &mut nth, _) = indices.select_nth_unstable_by(k, |&a, &b| slice[a].cmp(&slice[b]));
+        Some(&slice[nth])
+    }
+
+    /// Compresses each run of consecutive equal items into a `(value, run_length)` pair, clearing the vector.
+    pub fn compress_runs(&mut self) -> Vec<(T, usize)>
+    where
+        T: Clone + PartialEq,
+    {
+        let mut runs = Vec::default();
+        runs.extend_naive(
+            self.as_slice()
+                .chunk_by(PartialEq::eq)
+                .map(|run| (run[0].clone(), run.len())),
+        );
+        self.clear();
+        runs
+    }
+
+    /// Inserts clones of the items in `other` at the front of the vector, shifting every existing item right.
+    pub fn prepend_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        let (len, n) = (self.len, other.len());
+        self.realloc_if_spare_cap_lt_n(n);
+
+        // Until the front is filled, the vector only claims to be empty,
+        // so a panicking `clone` leaks the existing items instead of exposing uninitialized ones.
+        self.len = 0;
+        unsafe {
+            let p = self.items.as_mut_ptr();
+            ptr::copy(p, p.add(n), len);
+        }
+        for (slot, x) in self.items.iter_mut().zip(other) {
+            slot.write(x.clone());
+        }
+        self.len = len + n;
+    }
+
+    /// Pushes `x` onto the end of the vector, and if that makes it longer than `cap`, removes and returns the front item.
+    /// This makes the vector act as a fixed-size window over a stream of items.
+    pub fn push_bounded(&mut self, x: T, cap: usize) -> Option<T> {
+        self.push(x);
+        (self.len > cap).then(|| self.remove(0))
+    }
+
+    /// Returns clones of the items that don't appear in `other`, in the order they appear in the vector.
+    #[must_use]
+    pub fn difference(&self, other: &[T]) -> Self
+    where
+        T: Hash + Eq + Clone,
+    {
+        let excluded: HashSet<&T> = other.iter().collect();
+        let mut difference = Self::default();
+        difference.extend_naive(
+            self.as_slice()
+                .iter()
+                .filter(|x| !excluded.contains(x))
+                .cloned(),
+        );
+        difference
+    }
+
+    /// Returns clones of the distinct items that also appear in `other`, in the order they first appear in the vector.
+    #[must_use]
+    pub fn intersection(&self, other: &[T]) -> Self
+    where
+        T: Hash + Eq + Clone,
+    {
+        // Items are taken out of `remaining` as they're found, so each one is only kept once.
+        let mut remaining: HashSet<&T> = other.iter().collect();
+        let mut intersection = Self::default();
+        intersection.extend_naive(
+            self.as_slice()
+                .iter()
+                .filter(|x| remaining.remove(x))
+                .cloned(),
+        );
+        intersection
+    }
+
+    /// Reserves `additional` uninitialized slots at the end of the vector and passes them to `fill`,
+    /// which returns how many of the leading slots it initialized. Those items become part of the vector.
+    /// # Safety
+    /// `fill` must have initialized the first `n` slots it was given, where `n` is the count it returns.
+    /// The vector can't check this, which is why this isn't a safe function.
+    /// # Panics
+    /// Panics if `fill` returns a count larger than `additional`,
+    /// or if the ceiling set by `set_max_capacity` doesn't leave room for `additional` more items.
+    pub unsafe fn extend_with_slice_fn<F: FnOnce(&mut [MaybeUninit<T>]) -> usize>(
+        &mut self,
+        additional: usize,
+        fill: F,
+    ) {
+        self.realloc_if_spare_cap_lt_n(additional);
+        let spare = &mut self.items[self.len..self.len + additional];
+        let filled = fill(spare);
+        assert!(
+            filled <= additional,
+            "filled {filled} slots, but only {additional} were reserved"
+        );
+        self.len += filled;
+    }
+
+    /// Returns a reference to the item `ahead` places after `index`, if there is one.
+    #[must_use]
+    pub fn peek_at(&self, index: usize, ahead: usize) -> Option<&T> {
+        self.get(index.checked_add(ahead)?)
+    }
+
+    /// Reserves capacity for at least `percent`% of the current length more items, rounded up.
+    pub fn reserve_percent(&mut self, percent: u32) {
+        let additional = self.len.saturating_mul(percent as usize).div_ceil(100);
+        self.reserve(additional);
+    }
+
+    /// Panics if the length of the vector exceeds its capacity, to catch bugs in the unsafe code.
+    /// That's the only invariant the stored fields can check on their own.
+    /// Whether the first `len` items are really initialized, or whether a buffer larger than the
+    /// `set_max_capacity` ceiling was there before the ceiling rather than growing past it,
+    /// can't be checked without extra tracking.
+    #[doc(hidden)]
+    pub fn assert_invariants(&self) {
+        assert!(
+            self.len <= self.cap(),
+            "length {} exceeds capacity {}",
+            self.len,
+            self.cap()
+        );
+    }
+
+    /// Moves every item that satisfies `f` onto the end of `dest`, keeping the order of the items in both vectors.
+    /// `f` is called on each item exactly once, front to back.
+    pub fn move_matching_into<F: FnMut(&T) -> bool>(&mut self, dest: &mut Self, mut f: F) {
+        // The vector claims to be empty while items are being moved around,
+        // so a panicking `f` leaks the items instead of dropping any of them twice.
+        let len = std::mem::take(&mut self.len);
+        let p = self.items.as_mut_ptr().cast::<T>();
+        let mut kept = 0;
+        for i in 0..len {
+            unsafe {
+                let x = p.add(i);
+                if f(&*x) {
+                    dest.push(ptr::read(x));
+                } else {
+                    ptr::copy(x, p.add(kept), 1);
+                    kept += 1;
+                }
+            }
+        }
+        self.len = kept;
+    }
+
+    /// Exchanges the contents of the vector with those of `other`, including their capacities.
+    /// Per-vector settings move with the contents too, so the `set_max_capacity` ceiling and the
+    /// `reserve_predicted` growth prediction stay with the items they were set up for.
+    /// This only swaps fields, so it's O(1) and never reallocates.
+    pub const fn swap_contents(&mut self, other: &mut Self) {
+        std::mem::swap(self, other);
+    }
+
+    /// Distributes the items round-robin into `n` vectors, so item `i` is moved into vector `i % n`.
+    /// # Panics
+    /// Panics if `n` is zero.
+    #[must_use]
+    pub fn deinterleave(self, n: usize) -> std::vec::Vec<Self> {
+        assert!(n != 0, "cannot deinterleave into zero parts");
+        let mut parts: std::vec::Vec<Self> = (0..n)
+            .map(|i| Self::with_capacity((self.len + n - 1 - i) / n))
+            .collect();
+        for (i, x) in self.into_items().enumerate() {
+            parts[i % n].push(x);
+        }
+        parts
+    }
+
+    /// Records that the vector recently grew by `recent_growth` items, and reserves room for the growth expected next.
+    /// The expected growth is an exponentially smoothed average of the growths recorded so far,
+    /// where each new one is weighted one half, so a single burst doesn't throw the prediction off.
+    pub fn reserve_predicted(&mut self, recent_growth: usize) {
+        self.predicted_growth = if self.predicted_growth == 0 {
+            recent_growth
+        } else {
+            (self.predicted_growth + recent_growth).div_ceil(2)
+        };
+        self.reserve(self.predicted_growth);
+    }
+
+    /// Returns the length of the longest common subsequence of the vector and `other`.
+    /// # Notes
+    /// This is a dynamic programming algorithm that takes O(n·m) time, for vectors of lengths n and m,
+    /// but only O(m) extra space.
+    #[must_use]
+    pub fn lcs_len(&self, other: &[T]) -> usize
+    where
+        T: PartialEq,
+    {
+        // `row[j]` is the LCS length of the items seen so far and `other[..j]`.
+        let mut row = vec![0; other.len() + 1];
+        for a in self.as_slice() {
+            let mut diagonal = 0;
+            for (j, b) in other.iter().enumerate() {
+                let above = row[j + 1];
+                row[j + 1] = if a == b {
+                    diagonal + 1
+                } else {
+                    above.max(row[j])
+                };
+                diagonal = above;
+            }
+        }
+        row[other.len()]
+    }
+
+    /// Creates a vector of `count * stride` items, where every `stride`-th item (starting at index 0) is a clone of `value`
+    /// and the rest are `T::default()`.
+    /// # Panics
+    /// Panics if `count * stride` overflows.
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn with_stride(count: usize, stride: usize, value: T) -> Self
+    where
+        T: Clone + Default,
+    {
+        let len = count
+            .checked_mul(stride)
+            .expect("strided length overflowed");
+        let mut v = Self::with_capacity(len);
+        v.extend((0..len).map(|i| {
+            if i % stride == 0 {
+                value.clone()
+            } else {
+                T::default()
+            }
+        }));
+        v
+    }
+
+    /// Returns the difference between each pair of adjacent items, `v[i + 1] - v[i]`.
+    /// The result is one item shorter than the vector, or empty if the vector has fewer than 2 items.
+    #[must_use]
+    pub fn diff(&self) -> Self
+    where
+        T: std::ops::Sub<Output = T> + Copy,
+    {
+        let mut diffs = Self::with_capacity(self.len.saturating_sub(1));
+        diffs.extend(self.as_slice().windows(2).map(|w| w[1] - w[0]));
+        diffs
+    }
+
+    /// Pushes clones of `value` until the vector is `target_len` items long.
+    /// Unlike resizing, this never shortens the vector.
+    pub fn pad_to(&mut self, target_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        let missing = target_len.saturating_sub(self.len);
+        self.extend(std::iter::repeat_n(value, missing));
+    }
+
+    /// Reverses the byte order of every integer in the vector, converting them between endiannesses.
+    pub fn swap_byte_order(&mut self)
+    where
+        T: SwapBytes,
+    {
+        for x in self.as_slice_mut() {
+            *x = x.swap_bytes();
+        }
+    }
+
+    /// Returns clones of every `factor`-th item, starting with the first.
+    /// # Panics
+    /// Panics if `factor` is zero.
+    #[must_use]
+    pub fn downsample(&self, factor: usize) -> Self
+    where
+        T: Clone,
+    {
+        assert!(factor != 0, "downsampling factor must be non-zero");
+        let mut v = Self::with_capacity(self.len.div_ceil(factor));
+        v.extend(self.as_slice().iter().step_by(factor).cloned());
+        v
+    }
+
+    /// Moves the items into two boxed slices, split at the midpoint.
+    /// When the length is odd, the second half gets the extra item.
+    #[must_use]
+    pub fn into_halves(self) -> (Box<[T]>, Box<[T]>) {
+        let mid = self.len / 2;
+        let mut items = self.into_items();
+        let front = items.by_ref().take(mid).collect();
+        (front, items.collect())
+    }
+
+    /// Returns the mean and population variance of the vector, or `None` if it's empty.
+    /// Both are computed in a single pass with Welford's algorithm, which avoids the precision loss of summing squares.
+    #[must_use]
+    pub fn mean_variance(&self) -> Option<(f64, f64)>
+    where
+        T: Into<f64> + Copy,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        let (mut count, mut mean, mut squared_deviations) = (0.0, 0.0, 0.0);
+        for &x in self.as_slice() {
+            let x: f64 = x.into();
+            count += 1.0;
+            let delta = x - mean;
+            mean += delta / count;
+            squared_deviations += delta * (x - mean);
+        }
+        Some((mean, squared_deviations / count))
+    }
+
+    /// Keeps at most `max_per_value` occurrences of each distinct item, removing any later ones.
+    pub fn cap_duplicates(&mut self, max_per_value: usize)
+    where
+        T: Hash + Eq + Clone,
+    {
+        let mut seen = HashMap::new();
+        self.retain(|x| {
+            let count = seen.entry(x.clone()).or_insert(0);
+            *count += 1;
+            *count <= max_per_value
+        });
+    }
+
+    /// Returns the indices of the items, ordered by the key `key_fn` extracts from each item, without moving any items.
+    /// The sort is stable, and `key_fn` is called exactly once per item.
+    pub fn sort_indices_by_key<K: Ord, F: FnMut(&T) -> K>(&self, key_fn: F) -> Vec<usize> {
+        let keys: Box<[K]> = self.as_slice().iter().map(key_fn).collect();
+        let mut indices = Vec::with_capacity(keys.len());
+        indices.extend(0..keys.len());
+        indices
+            .as_slice_mut()
+            .sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+        indices
+    }
+
+    /// Iterates over each maximal run of consecutive equal items, as the run's index range and its value.
+    pub fn runs(&self) -> impl Iterator<Item = (Range<usize>, &T)>
+    where
+        T: PartialEq,
+    {
+        self.as_slice()
+            .chunk_by(PartialEq::eq)
+            .scan(0, |start, run| {
+                let range = *start..*start + run.len();
+                *start = range.end;
+                Some((range, &run[0]))
+            })
+    }
+
+    /// Converts `range` into the `start..end` range of indices it covers in the vector.
+    /// # Panics
+    /// Panics if the range is decreasing or extends past the end of the vector.
+    fn resolve_range<R: RangeBounds<usize>>(&self, range: &R) -> Range<usize> {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.checked_add(1).expect("range start overflowed"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i.checked_add(1).expect("range end overflowed"),
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            start <= end,
+            "range start (is {start}) should be <= end (is {end})"
+        );
+        assert!(
+            end <= self.len,
+            "range end (is {end}) should be <= len (is {})",
+            self.len
+        );
+        start..end
+    }
+
+    /// Drops the items in `range`, shifting every item after it left to close the gap.
+    /// # Panics
+    /// Panics if the range is decreasing or extends past the end of the vector.
+    pub fn delete_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        let Range { start, end } = self.resolve_range(&range);
+        let len = self.len;
+        let deleted = ptr::from_mut(&mut self.as_slice_mut()[start..end]);
+
+        // If dropping an item panics, the items after the range are leaked rather than exposed.
+        self.len = start;
+        unsafe {
+            ptr::drop_in_place(deleted);
+            let p = self.items.as_mut_ptr();
+            ptr::copy(p.add(end), p.add(start), len - end);
+        }
+        self.len = len - (end - start);
+    }
+
+    /// Awaits every item from `stream` and pushes it onto the vector.
+    /// Space is reserved up front for as many items as the stream's size hint promises.
+    #[cfg(feature = "futures")]
+    pub async fn extend_stream<S: futures::Stream<Item = T> + Unpin>(&mut self, stream: &mut S) {
+        use futures::StreamExt;
+
+        self.reserve(stream.size_hint().0);
+        while let Some(x) = stream.next().await {
+            self.push(x);
+        }
+    }
+
+    /// Treats the vector as a row-major array with dimensions `shape`,
+    /// and converts `flat_index` into the coordinates of the item it refers to.
+    /// Returns `None` if `shape` doesn't hold exactly `self.len()` items, or if `flat_index` is out of bounds.
+    #[must_use]
+    pub fn to_coords(&self, flat_index: usize, shape: &[usize]) -> Option<Vec<usize>> {
+        let size = shape
+            .iter()
+            .try_fold(1usize, |acc, &d| acc.checked_mul(d))?;
+        if size != self.len || flat_index >= self.len {
+            return None;
+        }
+        let mut coords = Vec::with_capacity(shape.len());
+        coords.extend(shape.iter().map(|_| 0));
+        let mut rest = flat_index;
+        for (coord, &dim) in coords.as_slice_mut().iter_mut().zip(shape).rev() {
+            *coord = rest % dim;
+            rest /= dim;
+        }
+        Some(coords)
+    }
+
+    /// Sorts the vector with a stable, bottom-up merge sort.
+    /// Exactly one scratch buffer the size of the vector is allocated, and runs are merged back and forth between the two.
+    pub fn merge_sort(&mut self)
+    where
+        T: Ord + Clone,
+    {
+        /// Merges each pair of adjacent `width`-item runs from `from` into the same place in `to`.
+        fn merge_pass<T: Ord + Clone>(from: &[T], to: &mut [T], width: usize) {
+            let len = from.len();
+            for start in (0..len).step_by(2 * width) {
+                let mid = (start + width).min(len);
+                let end = (start + 2 * width).min(len);
+                let (mut i, mut j) = (start, mid);
+                for slot in &mut to[start..end] {
+                    // Taking from the left run on ties keeps the sort stable.
+                    let take_left = j == end || (i < mid && from[i] <= from[j]);
+                    let src = if take_left { &mut i } else { &mut j };
+                    slot.clone_from(&from[*src]);
+                    *src += 1;
+                }
+            }
+        }
+
+        let data = self.as_slice_mut();
+        let mut scratch: Box<[T]> = data.into();
+        let mut in_scratch = false;
+        let mut width = 1;
+        while width < data.len() {
+            if in_scratch {
+                merge_pass(&scratch, data, width);
+            } else {
+                merge_pass(data, &mut scratch, width);
+            }
+            in_scratch = !in_scratch;
+            width *= 2;
+        }
+        if in_scratch {
+            data.clone_from_slice(&scratch);
+        }
+    }
+
+    /// Rotates the vector into its lexicographically smallest rotation,
+    /// so that any two rotations of the same cyclic sequence end up equal.
+    pub fn canonical_rotation(&mut self)
+    where
+        T: Ord,
+    {
+        let items = self.as_slice();
+        let len = items.len();
+
+        // `i` and `j` are the two best candidate starts so far, and `k` is how far they've been found to match.
+        // Whenever they differ, the worse candidate can skip past everything that was just compared.
+        let (mut i, mut j, mut k) = (0, 1, 0);
+        while i < len && j < len && k < len {
+            match items[(i + k) % len].cmp(&items[(j + k) % len]) {
+                Ordering::Equal => k += 1,
+                Ordering::Greater => {
+                    i += k + 1;
+                    k = 0;
+                }
+                Ordering::Less => {
+                    j += k + 1;
+                    k = 0;
+                }
+            }
+            if i == j {
+                j += 1;
+            }
+        }
+        let start = i.min(j);
+        if start < len {
+            self.as_slice_mut().rotate_left(start);
+        }
+    }
+
+    /// Returns the absolute difference between each item and `scalar`.
+    /// The smaller value is always subtracted from the larger one, so this works for unsigned integers too.
+    #[must_use]
+    pub fn abs_diff_scalar(&self, scalar: T) -> Self
+    where
+        T: PartialOrd + std::ops::Sub<Output = T> + Copy,
+    {
+        let mut diffs = Self::with_capacity(self.len);
+        diffs.extend(
+            self.as_slice()
+                .iter()
+                .map(|&x| if x > scalar { x - scalar } else { scalar - x }),
+        );
+        diffs
+    }
+
+    /// Truncates the vector just before the first item equal to `value`, dropping it and everything after it.
+    /// Returns whether `value` was found; the vector is left unchanged if not.
+    pub fn truncate_at(&mut self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let Some(index) = self.as_slice().iter().position(|x| x == value) else {
+            return false;
+        };
+        self.truncate(index);
+        true
+    }
+
+    /// Splits the vector into consecutive slices with the given `sizes`, in order.
+    /// Returns `None` unless the sizes add up to exactly `self.len()`.
+    #[must_use]
+    pub fn split_by_sizes(&self, sizes: &[usize]) -> Option<std::vec::Vec<&[T]>> {
+        let mut rest = self.as_slice();
+        let mut pieces = std::vec::Vec::with_capacity(sizes.len());
+        for &size in sizes {
+            let (piece, tail) = rest.split_at_checked(size)?;
+            pieces.push(piece);
+            rest = tail;
+        }
+        rest.is_empty().then_some(pieces)
+    }
+
+    /// Returns the Levenshtein edit distance between the vector and `other`,
+    /// the fewest single-item insertions, deletions, and substitutions that turn one into the other.
+    /// # Notes
+    /// This takes O(n·m) time for vectors of lengths n and m, but only keeps one row of the table, using O(m) extra space.
+    #[must_use]
+    pub fn levenshtein(&self, other: &[T]) -> usize
+    where
+        T: PartialEq,
+    {
+        // `row[j]` is the distance between the items seen so far and `other[..j]`.
+        let mut row: std::vec::Vec<usize> = (0..=other.len()).collect();
+        for (i, a) in self.as_slice().iter().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+            for (j, b) in other.iter().enumerate() {
+                let above = row[j + 1];
+                row[j + 1] = if a == b {
+                    diagonal
+                } else {
+                    1 + diagonal.min(above).min(row[j])
+                };
+                diagonal = above;
+            }
+        }
+        row[other.len()]
+    }
+
+    /// Iterates over each run of consecutive equal items, as the run's value and length.
+    /// This is a borrowing, streaming form of run-length encoding.
+    pub fn iter_runs(&self) -> impl Iterator<Item = (&T, usize)>
+    where
+        T: PartialEq,
+    {
+        self.runs().map(|(range, x)| (x, range.len()))
+    }
+
+    /// Replaces the item at `index` with `new`, but only if it's currently equal to `expected`.
+    /// # Errors
+    /// Returns `new` back if the item isn't equal to `expected`.
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn compare_replace(&mut self, index: usize, expected: &T, new: T) -> Result<T, T>
+    where
+        T: PartialEq,
+    {
+        let len = self.len;
+        let Some(slot) = self.get_mut(index) else {
+            panic!("index (is {index}) should be < len (is {len})");
+        };
+        if slot == expected {
+            Ok(std::mem::replace(slot, new))
+        } else {
+            Err(new)
+        }
+    }
+
+    /// Moves every item that satisfies `f` into a new vector, keeping the order of the items in both vectors.
+    #[must_use]
+    pub fn partition_off<F: FnMut(&T) -> bool>(&mut self, f: F) -> Self {
+        let mut matching = Self::default();
+        self.move_matching_into(&mut matching, f);
+        matching
+    }
+
+    /// Returns the sum of each item multiplied by its weight in `weights`, or `None` if their lengths differ.
+    /// The lengths are checked before any arithmetic is done.
+    #[must_use]
+    pub fn weighted_sum(&self, weights: &[T]) -> Option<T>
+    where
+        T: std::iter::Sum + std::ops::Mul<Output = T> + Copy,
+    {
+        self.dot(weights)
+    }
+
+    /// Creates a vector by applying `f` to each index in `range`, with exactly enough capacity for the result.
+    pub fn range_map<F: FnMut(usize) -> T>(range: Range<usize>, f: F) -> Self {
+        let mut v = Self::with_capacity(range.len());
+        v.extend(range.map(f));
+        v
+    }
+
+    /// Returns the bitwise AND of every item, or `None` if the vector is empty.
+    #[must_use]
+    pub fn fold_and(&self) -> Option<T>
+    where
+        T: std::ops::BitAnd<Output = T> + Copy,
+    {
+        self.as_slice().iter().copied().reduce(|a, b| a & b)
+    }
+
+    /// Returns the bitwise OR of every item, or `None` if the vector is empty.
+    #[must_use]
+    pub fn fold_or(&self) -> Option<T>
+    where
+        T: std::ops::BitOr<Output = T> + Copy,
+    {
+        self.as_slice().iter().copied().reduce(|a, b| a | b)
+    }
+
+    /// Returns the bitwise XOR of every item, or `None` if the vector is empty.
+    #[must_use]
+    pub fn fold_xor(&self) -> Option<T>
+    where
+        T: std::ops::BitXor<Output = T> + Copy,
+    {
+        self.as_slice().iter().copied().reduce(|a, b| a ^ b)
+    }
+
+    /// Rotates the vector left by `offset` places, or right if `offset` is negative.
+    /// The offset wraps around the length of the vector, so any offset is valid.
+    pub fn rotate(&mut self, offset: isize) {
+        if self.is_empty() {
+            return;
+        }
+        let shift = offset.unsigned_abs() % self.len;
+        if offset >= 0 {
+            self.as_slice_mut().rotate_left(shift);
+        } else {
+            self.as_slice_mut().rotate_right(shift);
+        }
+    }
+
+    /// Creates a vector by concatenating clones of `parts`, with a clone of `sep` between each of them.
+    /// The total length is computed first, so the vector is allocated only once.
+    pub fn join_with<I, J>(parts: I, sep: &[T]) -> Self
+    where
+        I: IntoIterator<Item = J>,
+        J: AsRef<[T]>,
+        T: Clone,
+    {
+        let parts: std::vec::Vec<J> = parts.into_iter().collect();
+        let items: usize = parts.iter().map(|p| p.as_ref().len()).sum();
+        let seps = sep.len() * parts.len().saturating_sub(1);
+        let mut joined = Self::with_capacity(items + seps);
+        for (i, part) in parts.iter().enumerate() {
+            if i != 0 {
+                joined.extend(sep.iter().cloned());
+            }
+            joined.extend(part.as_ref().iter().cloned());
+        }
+        joined
+    }
+
+    /// Counts how many items fall into each of `bins` equal-width bins spanning `[min, max]`.
+    /// Items below `min` are counted in the first bin, and items at or above `max` in the last one.
+    /// # Panics
+    /// Panics if `bins` is zero.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn histogram(&self, min: f64, max: f64, bins: usize) -> Vec<usize>
+    where
+        T: Into<f64> + Copy,
+    {
+        assert!(bins != 0, "histogram needs at least one bin");
+        let mut counts = Vec::with_capacity(bins);
+        counts.extend(std::iter::repeat_n(0, bins));
+        let counts_slice = counts.as_slice_mut();
+        let scale = bins as f64 / (max - min);
+        for &x in self.as_slice() {
+            // Casting saturates, so anything below `min` (or NaN) lands in the first bin.
+            let bin = ((x.into() - min) * scale) as usize;
+            counts_slice[bin.min(bins - 1)] += 1;
+        }
+        counts
+    }
+
+    /// Replaces the items in `range` with a single `value`, shifting the items after it to close the gap.
+    /// If the range is empty, `value` is inserted at its start instead.
+    /// # Panics
+    /// Panics if the range is decreasing or extends past the end of the vector.
+    pub fn replace_range_with<R: RangeBounds<usize>>(&mut self, range: R, value: T) {
+        let Range { start, end } = self.resolve_range(&range);
+        if start == end {
+            self.insert(start, value);
+        } else {
+            self.as_slice_mut()[start] = value;
+            self.delete_range(start + 1..end);
+        }
+    }
+
+    /// Swaps the items in range `a` with the items in range `b`.
+    /// # Errors
+    /// Returns an error, leaving the vector unchanged, if the ranges have different lengths, overlap,
+    /// or don't lie within the vector.
+    pub fn swap_ranges(&mut self, a: Range<usize>, b: Range<usize>) -> Result<(), RangeSwapError> {
+        for range in [&a, &b] {
+            if self.as_slice().get(range.clone()).is_none() {
+                return Err(RangeSwapError::OutOfBounds {
+                    range: range.clone(),
+                    len: self.len,
+                });
+            }
+        }
+        if a.len() != b.len() {
+            return Err(RangeSwapError::LengthMismatch {
+                a: a.len(),
+                b: b.len(),
+            });
+        }
+        if a.start < b.end && b.start < a.end {
+            return Err(RangeSwapError::Overlap);
+        }
+        unsafe {
+            let p = self.items.as_mut_ptr();
+            ptr::swap_nonoverlapping(p.add(a.start), p.add(b.start), a.len());
+        }
+        Ok(())
+    }
+
+    /// Replaces each item with the sum of itself and every item after it.
+    pub fn suffix_sum(&mut self)
+    where
+        T: std::ops::AddAssign + Copy,
+    {
+        let slice = self.as_slice_mut();
+        for i in (1..slice.len()).rev() {
+            let after = slice[i];
+            slice[i - 1] += after;
+        }
+    }
+
+    /// Returns the full discrete convolution of the vector with `kernel`, of length `len + kernel.len() - 1`.
+    /// The result is empty if either input is empty.
+    #[must_use]
+    pub fn convolve(&self, kernel: &[T]) -> Self
+    where
+        T: std::ops::Mul<Output = T> + std::iter::Sum + Copy + Default,
+    {
+        let signal = self.as_slice();
+        if signal.is_empty() || kernel.is_empty() {
+            return Self::default();
+        }
+        let out_len = signal.len() + kernel.len() - 1;
+        let mut out = Self::with_capacity(out_len);
+        out.extend((0..out_len).map(|k| {
+            let first = k.saturating_sub(kernel.len() - 1);
+            let last = k.min(signal.len() - 1);
+            (first..=last).map(|i| signal[i] * kernel[k - i]).sum()
+        }));
+        out
+    }
+
+    /// Expands `(value, count)` pairs into a vector holding `count` clones of each value, in order.
+    /// The total length is reserved up front, so the vector is allocated only once.
+    pub fn from_runs(runs: &[(T, usize)]) -> Self
+    where
+        T: Clone,
+    {
+        let mut v = Self::with_capacity(runs.iter().map(|&(_, count)| count).sum());
+        for (value, count) in runs {
+            v.extend(std::iter::repeat_n(value.clone(), *count));
+        }
+        v
+    }
+
+    /// Returns the most frequent item, or `None` if the vector is empty.
+    /// Ties go to whichever of the tied items appears first in the vector.
+    #[must_use]
+    pub fn mode(&self) -> Option<&T>
+    where
+        T: Hash + Eq,
+    {
+        // Maps each item to its count and the index where it first appears.
+        let mut freq: HashMap<&T, (usize, usize)> = HashMap::new();
+        let mut best: Option<(&T, usize, usize)> = None;
+        for (i, x) in self.as_slice().iter().enumerate() {
+            let entry = freq.entry(x).or_insert((0, i));
+            entry.0 += 1;
+            let (count, first) = *entry;
+            if best.is_none_or(|(_, c, f)| count > c || (count == c && first < f)) {
+                best = Some((x, count, first));
+            }
+        }
+        best.map(|(x, _, _)| x)
+    }
+
+    /// Pushes each `Ok(Some(x))` returned by `f`, stopping when it returns `Ok(None)`.
+    /// # Errors
+    /// Returns the first error `f` produces; any items pushed before it are left in the vector.
+    pub fn try_extend_with<E, F: FnMut() -> Result<Option<T>, E>>(
+        &mut self,
+        mut f: F,
+    ) -> Result<(), E> {
+        while let Some(x) = f()? {
+            self.push(x);
+        }
+        Ok(())
+    }
+
+    /// Removes the items at `indices`, which may be in any order, and returns them sorted by index.
+    /// The remaining items keep their order and are compacted in a single pass.
+    /// # Panics
+    /// Panics if any index is repeated or out of bounds; the vector is left unchanged in that case.
+    #[must_use]
+    pub fn remove_indices(&mut self, indices: &[usize]) -> Self {
+        let len = self.len;
+        let mut marked = vec![false; len];
+        for &i in indices {
+            assert!(i < len, "index (is {i}) should be < len (is {len})");
+            assert!(!marked[i], "index {i} is given more than once");
+            marked[i] = true;
+        }
+        let mut removed = Self::with_capacity(indices.len());
+        let mut kept = 0;
+        let p = self.items.as_mut_ptr();
+        for (i, &is_marked) in marked.iter().enumerate() {
+            // Each initialized item is moved exactly once, either out into `removed` or down into the gap.
+            unsafe {
+                if is_marked {
+                    removed.push(p.add(i).read().assume_init());
+                } else {
+                    ptr::copy(p.add(i), p.add(kept), 1);
+                    kept += 1;
+                }
+            }
+        }
+        self.len = kept;
+        removed
+    }
+
+    /// Returns the largest item in each window of `window` consecutive items, front to back.
+    /// A monotonic deque of candidate indices keeps this O(n) regardless of the window size.
+    /// # Panics
+    /// Panics if `window` is zero or greater than the length of the vector.
+    #[must_use]
+    pub fn sliding_max(&self, window: usize) -> Self
+    where
+        T: Ord + Clone,
+    {
+        let slice = self.as_slice();
+        assert!(window != 0, "window size must be non-zero");
+        assert!(
+            window <= slice.len(),
+            "window (is {window}) should be <= len (is {})",
+            slice.len()
+        );
+        // Holds indices whose items are in decreasing order, so the front is always the window's maximum.
+        let mut candidates = std::collections::VecDeque::with_capacity(window);
+        let mut maxima = Self::with_capacity(slice.len() - window + 1);
+        for (i, x) in slice.iter().enumerate() {
+            while candidates.back().is_some_and(|&j| slice[j] <= *x) {
+                candidates.pop_back();
+            }
+            candidates.push_back(i);
+            if candidates.front().is_some_and(|&j| j + window <= i) {
+                candidates.pop_front();
+            }
+            if i + 1 >= window {
+                maxima.push(slice[candidates[0]].clone());
+            }
+        }
+        maxima
+    }
+
+    /// Returns the top `n` items of the vector treated as a stack, i.e. its last `n` items, front to back.
+    /// If `n` is greater than the length of the vector, every item is returned.
+    #[must_use]
+    pub fn peek_top(&self, n: usize) -> &[T] {
+        let slice = self.as_slice();
+        &slice[slice.len().saturating_sub(n)..]
+    }
+
+    /// Drops the first `n` items, moves the rest to the front, and fills the tail with `T::default()`.
+    /// The length is unchanged; if `n` is at least the length, every item is replaced with the default.
+    pub fn shift_left(&mut self, n: usize)
+    where
+        T: Default,
+    {
+        let slice = self.as_slice_mut();
+        let n = n.min(slice.len());
+        slice[..n].fill_with(T::default);
+        slice.rotate_left(n);
+    }
+
+    /// Returns the Jaccard similarity of the vector and `other` treated as sets, `|A ∩ B| / |A ∪ B|`.
+    /// Two empty sets are considered identical, giving a similarity of 1.0.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn jaccard(&self, other: &[T]) -> f64
+    where
+        T: Hash + Eq + Clone,
+    {
+        let a: HashSet<&T> = self.as_slice().iter().collect();
+        let b: HashSet<&T> = other.iter().collect();
+        let union = a.union(&b).count();
+        if union == 0 {
+            return 1.0;
+        }
+        a.intersection(&b).count() as f64 / union as f64
+    }
+
+    /// Sets a ceiling on the vector's capacity, so that it never grows past `max` items.
+    /// An existing allocation larger than `max` is kept and may be filled, but can't grow any further,
+    /// so the vector can hold up to `max` items or its current capacity, whichever is larger.
+    /// Reservations are clamped to that limit, while `push` and other methods that store items past it panic.
+    /// Once the limit is reached, use `push_capped` to push without panicking.
+    /// The ceiling is a per-vector setting, so `swap_contents` moves it along with the items.
+    pub const fn set_max_capacity(&mut self, max: usize) {
+        self.max_capacity = Some(max);
+    }
+
+    /// Pushes `x` onto the vector unless that would take it past the limit set by `set_max_capacity`.
+    /// # Errors
+    /// Returns `x` back if the vector already holds as many items as the limit allows.
+    pub fn push_capped(&mut self, x: T) -> Result<(), T> {
+        if self.capacity_limit().is_some_and(|limit| self.len >= limit) {
+            return Err(x);
+        }
+        self.push(x);
+        Ok(())
+    }
+
+    /// Returns a mask where item `i` is whether items `i` and `i + 1` are equal, of length `len - 1`.
+    /// The mask is empty if the vector has fewer than two items.
+    #[must_use]
+    pub fn equal_neighbors_mask(&self) -> Vec<bool>
+    where
+        T: PartialEq,
+    {
+        let slice = self.as_slice();
+        let mut mask = Vec::with_capacity(slice.len().saturating_sub(1));
+        mask.extend(slice.windows(2).map(|pair| pair[0] == pair[1]));
+        mask
+    }
+
+    /// Pushes each item from `iter` that isn't already in the vector, including items pushed by this call.
+    /// Items are pushed in the order `iter` yields them.
+    pub fn extend_unique(&mut self, iter: impl IntoIterator<Item = T>)
+    where
+        T: Hash + Eq + Clone,
+    {
+        let mut seen: HashSet<T> = self.as_slice().iter().cloned().collect();
+        for x in iter {
+            if seen.insert(x.clone()) {
+                self.push(x);
+            }
+        }
+    }
+
+    /// Returns where each bucket starts if the items were stably sorted by `bucket_of`, followed by the length.
+    /// The result has `num_buckets + 1` offsets, so bucket `b` spans `offsets[b]..offsets[b + 1]`.
+    /// # Panics
+    /// Panics if `bucket_of` returns a bucket that isn't less than `num_buckets`.
+    #[must_use]
+    pub fn bucket_offsets(
+        &self,
+        num_buckets: usize,
+        bucket_of: impl Fn(&T) -> usize,
+    ) -> Vec<usize> {
+        // The extra trailing zero count turns the exclusive offsets into `num_buckets + 1` of them.
+        let mut counts = vec![0; num_buckets + 1];
+        for x in self.as_slice() {
+            let bucket = bucket_of(x);
+            assert!(
+                bucket < num_buckets,
+                "bucket (is {bucket}) should be < num_buckets (is {num_buckets})"
+            );
+            counts[bucket] += 1;
+        }
+        Vec::from_counts(&counts)
+    }
+
+    /// Returns the distinct items of the vector in sorted order, leaving the vector unchanged.
+    #[must_use]
+    pub fn sorted_unique(&self) -> Self
+    where
+        T: Ord + Clone,
+    {
+        let mut items = self.as_slice().to_vec();
+        items.sort();
+        items.dedup();
+        let mut unique = Self::with_capacity(items.len());
+        unique.extend(items.into_iter());
+        unique
+    }
+
+    /// Moves the front item to the back and returns it, or `None` if the vector is empty.
+    /// Calling this repeatedly cycles through every item in order.
+    /// Each call shifts every item, so it takes O(n) time.
+    pub fn round_robin_next(&mut self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.as_slice_mut().rotate_left(1);
+        self.last()
+    }
+
+    /// Truncates the vector to `floor(cap * fraction)` items if it's longer than that, dropping the tail.
+    /// A negative or NaN `fraction` clears the vector.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn truncate_to_capacity_fraction(&mut self, fraction: f64) {
+        // Casting saturates, so out-of-range products end up as 0 or `usize::MAX`.
+        self.truncate((self.cap() as f64 * fraction).floor() as usize);
+    }
+
+    /// Returns whether the items of `needle` appear in the vector in the same order, not necessarily next to each other.
+    /// An empty needle is a subsequence of every vector.
+    #[must_use]
+    pub fn contains_subsequence(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut matched = 0;
+        for x in self.as_slice() {
+            if matched == needle.len() {
+                break;
+            }
+            if *x == needle[matched] {
+                matched += 1;
+            }
+        }
+        matched == needle.len()
+    }
+
+    /// Returns the `p`th percentile of the items, from 0.0 to 100.0, or `None` if the vector is empty.
+    /// Percentiles that fall between two items are linearly interpolated, and `p` is clamped to that range.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn percentile(&self, p: f64) -> Option<f64>
+    where
+        T: Into<f64> + Copy,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        let mut sorted: std::vec::Vec<f64> = self.as_slice().iter().map(|&x| x.into()).collect();
+        sorted.sort_by(f64::total_cmp);
+        let rank = p.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64;
+        let below = rank.floor() as usize;
+        let above = rank.ceil() as usize;
+        let weight = rank - rank.floor();
+        Some((sorted[above] - sorted[below]).mul_add(weight, sorted[below]))
+    }
+
+    /// Splits the vector into the pieces between items that satisfy `f`, moving each item into its piece.
+    /// The matching items are dropped. Like `slice::split`, adjacent matches produce empty pieces.
+    pub fn split_owned<F: FnMut(&T) -> bool>(self, mut f: F) -> std::vec::Vec<Self> {
+        let mut pieces = std::vec::Vec::new();
+        let mut piece = Self::default();
+        for x in self.into_items() {
+            if f(&x) {
+                pieces.push(std::mem::take(&mut piece));
+            } else {
+                piece.push(x);
+            }
+        }
+        pieces.push(piece);
+        pieces
+    }
+
+    /// Sorts the vector in place with a stable insertion sort, without allocating.
+    /// This takes O(n²) time in general, but is fast for short or nearly sorted vectors.
+    pub fn insertion_sort(&mut self)
+    where
+        T: Ord,
+    {
+        let slice = self.as_slice_mut();
+        for i in 1..slice.len() {
+            // Only move past strictly greater items, so equal items keep their order.
+            let mut j = i;
+            while j > 0 && slice[j - 1] > slice[j] {
+                slice.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
+    /// Returns the length of the longest strictly increasing subsequence of the vector.
+    /// This uses patience sorting, so it takes O(n log n) time.
+    #[must_use]
+    pub fn lis_len(&self) -> usize
+    where
+        T: Ord + Clone,
+    {
+        // `tails[k]` is the smallest item that ends an increasing subsequence of length `k + 1`.
+        let mut tails: std::vec::Vec<T> = std::vec::Vec::new();
+        for x in self.as_slice() {
+            let k = tails.partition_point(|tail| tail < x);
+            if k == tails.len() {
+                tails.push(x.clone());
+            } else {
+                tails[k] = x.clone();
+            }
+        }
+        tails.len()
+    }
+
+    /// Returns an iterator over disjoint mutable pairs of adjacent items, `(v[0], v[1])`, `(v[2], v[3])`, and so on.
+    /// A trailing item without a partner is skipped.
+    pub fn pairs_mut(&mut self) -> impl Iterator<Item = (&mut T, &mut T)> {
+        self.as_slice_mut().chunks_exact_mut(2).map(|pair| {
+            let (first, second) = pair.split_at_mut(1);
+            (&mut first[0], &mut second[0])
+        })
+    }
+
+    /// Returns each distinct item with how many times it appears, most frequent first.
+    /// Items that appear equally often are ordered by where they first appear.
+    #[must_use]
+    pub fn by_frequency(&self) -> Vec<(T, usize)>
+    where
+        T: Hash + Eq + Clone,
+    {
+        // Maps each distinct item to its position in `counted`, which is in order of first appearance.
+        let mut positions: HashMap<&T, usize> = HashMap::new();
+        let mut counted: std::vec::Vec<(T, usize)> = std::vec::Vec::new();
+        for x in self.as_slice() {
+            let next = counted.len();
+            let position = *positions.entry(x).or_insert(next);
+            if position == next {
+                counted.push((x.clone(), 0));
+            }
+            counted[position].1 += 1;
+        }
+        // The sort is stable, so ties stay in order of first appearance.
+        counted.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        let mut frequencies = Vec::with_capacity(counted.len());
+        frequencies.extend(counted.into_iter());
+        frequencies
+    }
+
+    /// Clamps the length of the vector to its capacity if it somehow exceeds it, which would mean there's a bug.
+    /// Returns whether the length needed clamping.
+    /// This is only a safety net for development, and won't fix whatever caused the desync.
+    #[cfg(feature = "debug-checks")]
+    pub const fn repair(&mut self) -> bool {
+        let cap = self.cap();
+        let desynced = self.len > cap;
+        if desynced {
+            self.len = cap;
+        }
+        desynced
+    }
+
+    /// Returns `min(k, len)` items chosen uniformly at random, using reservoir sampling in a single pass.
+    /// The sampled items aren't in any particular order.
+    #[cfg(feature = "rand")]
+    #[must_use]
+    pub fn reservoir_sample(&self, k: usize, rng: &mut impl rand::Rng) -> Self
+    where
+        T: Clone,
+    {
+        let slice = self.as_slice();
+        let mut sample = Self::with_capacity(k.min(slice.len()));
+        for (i, x) in slice.iter().enumerate() {
+            if i < k {
+                sample.push(x.clone());
+            } else {
+                // Item `i` replaces a sampled item with probability `k / (i + 1)`.
+                let j = rng.random_range(0..=i);
+                if j < k {
+                    sample.as_slice_mut()[j] = x.clone();
+                }
+            }
+        }
+        sample
+    }
+
+    /// Drops the last `n` items, moves the rest to the back, and fills the head with `T::default()`.
+    /// The length is unchanged; if `n` is at least the length, every item is replaced with the default.
+    pub fn shift_right(&mut self, n: usize)
+    where
+        T: Default,
+    {
+        let slice = self.as_slice_mut();
+        let n = n.min(slice.len());
+        let kept = slice.len() - n;
+        slice[kept..].fill_with(T::default);
+        slice.rotate_right(n);
+    }
+
+    /// Returns the smaller of each pair of items from the vector and `other`, stopping at the shorter of the two.
+    /// Items from the vector are kept when the pair is equal or incomparable.
+    #[must_use]
+    pub fn zip_min(&self, other: &[T]) -> Self
+    where
+        T: PartialOrd + Clone,
+    {
+        self.zip_select(other, |a, b| b < a)
+    }
+
+    /// Returns the larger of each pair of items from the vector and `other`, stopping at the shorter of the two.
+    /// Items from the vector are kept when the pair is equal or incomparable.
+    #[must_use]
+    pub fn zip_max(&self, other: &[T]) -> Self
+    where
+        T: PartialOrd + Clone,
+    {
+        self.zip_select(other, |a, b| b > a)
+    }
+
+    /// Clones the item from `other` wherever `take_other` is true, or from the vector otherwise.
+    fn zip_select<F: Fn(&T, &T) -> bool>(&self, other: &[T], take_other: F) -> Self
+    where
+        T: Clone,
+    {
+        let pairs = self.as_slice().iter().zip(other);
+        let mut selected = Self::with_capacity(pairs.len());
+        selected.extend(pairs.map(|(a, b)| if take_other(a, b) { b } else { a }.clone()));
+        selected
+    }
+
+    /// Returns the index of the item closest to `target`, or `None` if the vector is empty.
+    /// Ties go to the earliest item, and like `abs_diff_scalar` this works for unsigned integers too.
+    #[must_use]
+    pub fn nearest(&self, target: &T) -> Option<usize>
+    where
+        T: PartialOrd + std::ops::Sub<Output = T> + Copy,
+    {
+        let target = *target;
+        let mut best: Option<(usize, T)> = None;
+        for (i, &x) in self.as_slice().iter().enumerate() {
+            let diff = if x > target { x - target } else { target - x };
+            if best.is_none_or(|(_, best_diff)| diff < best_diff) {
+                best = Some((i, diff));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// Keeps the longest suffix of the vector whose total weight is at most `max_weight`, dropping the items before it.
+    pub fn retain_tail_by_weight<F: FnMut(&T) -> usize>(
+        &mut self,
+        max_weight: usize,
+        mut weight: F,
+    ) {
+        let mut total = 0usize;
+        let kept = self
+            .as_slice()
+            .iter()
+            .rev()
+            .take_while(|x| {
+                total = total.saturating_add(weight(x));
+                total <= max_weight
+            })
+            .count();
+        self.delete_range(..self.len - kept);
+    }
+
+    /// Stably sorts the vector by the key `key_fn` extracts from each item, calling it exactly once per item.
+    /// The sorted order is computed with `sort_indices_by_key` and then applied with `apply_permutation`.
+    /// # Panics
+    /// Only panics if the computed order isn't a permutation, which would be a bug.
+    pub fn sort_by_key_gather<K: Ord, F: FnMut(&T) -> K>(&mut self, key_fn: F)
+    where
+        T: Clone,
+    {
+        let order = self.sort_indices_by_key(key_fn);
+        self.apply_permutation(order.as_slice())
+            .expect("sorted indices form a permutation");
+    }
+
+    /// Consumes the vector, moving each item into `f` and collecting every item of the iterators it returns.
+    pub fn flat_map_into<U, I: IntoIterator<Item = U>, F: FnMut(T) -> I>(self, f: F) -> Vec<U> {
+        let mut flattened = Vec::with_capacity(self.len);
+        flattened.extend(self.into_items().flat_map(f));
+        flattened
+    }
+
+    /// Returns the `k` largest items in descending order, or every item if there are fewer than `k`.
+    /// A min-heap of at most `k` items is kept while scanning, so this takes O(n log k) time.
+    #[must_use]
+    pub fn top_k(&self, k: usize) -> Self
+    where
+        T: Ord + Clone,
+    {
+        use std::cmp::Reverse;
+        if k == 0 {
+            return Self::default();
+        }
+        // The smallest of the largest items seen so far sits at the top of the heap.
+        let mut heap = std::collections::BinaryHeap::with_capacity(k);
+        for x in self.as_slice() {
+            if heap.len() < k {
+                heap.push(Reverse(x));
+            } else if heap.peek().is_some_and(|Reverse(smallest)| x > *smallest) {
+                heap.pop();
+                heap.push(Reverse(x));
+            }
+        }
+        let mut largest = Self::with_capacity(heap.len());
+        largest.extend(
+            heap.into_sorted_vec()
+                .into_iter()
+                .map(|Reverse(x)| x.clone()),
+        );
+        largest
+    }
+
+    /// Returns whether `other` holds the same items as the vector in reverse order.
+    #[must_use]
+    pub fn is_reverse_of(&self, other: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let slice = self.as_slice();
+        slice.len() == other.len() && slice.iter().eq(other.iter().rev())
+    }
+
+    /// Returns a copy of the vector with `stride - 1` copies of `T::default()` after each item, for zero-stuffing signals.
+    /// # Panics
+    /// Panics if `stride` is zero or `len * stride` overflows.
+    #[must_use]
+    pub fn expand_with_default(&self, stride: usize) -> Self
+    where
+        T: Clone + Default,
+    {
+        assert!(stride != 0, "stride must be non-zero");
+        let len = self
+            .len
+            .checked_mul(stride)
+            .expect("expanded length overflowed");
+        let mut expanded = Self::with_capacity(len);
+        for x in self.as_slice() {
+            expanded.push(x.clone());
+            expanded.extend(repeat_with(T::default).take(stride - 1));
+        }
+        expanded
+    }
+
+    /// Returns the index range of the longest run of consecutive items that satisfy `f`, or `None` if none do.
+    /// Ties go to the earliest run.
+    pub fn longest_range_where<F: FnMut(&T) -> bool>(&self, mut f: F) -> Option<Range<usize>> {
+        let mut best: Option<Range<usize>> = None;
+        let mut start = None;
+        for (i, x) in self.as_slice().iter().enumerate() {
+            if f(x) {
+                let run = *start.get_or_insert(i)..i + 1;
+                if best.as_ref().is_none_or(|b| run.len() > b.len()) {
+                    best = Some(run);
+                }
+            } else {
+                start = None;
+            }
+        }
+        best
+    }
+}
+
+impl Vec<u8> {
+    /// Run-length encodes the bytes in the vector as `(byte, run_length)` pairs.
+    #[must_use]
+    pub fn rle_encode(&self) -> Vec<(u8, usize)> {
+        let mut pairs = Vec::default();
+        pairs.extend(
+            self.as_slice()
+                .chunk_by(u8::eq)
+                .map(|run| (run[0], run.len())),
+        );
+        pairs
+    }
+
+    /// Decodes `(byte, run_length)` pairs produced by `rle_encode` back into bytes.
+    #[must_use]
+    pub fn rle_decode(pairs: &[(u8, usize)]) -> Self {
+        let mut bytes = Self::with_capacity(pairs.iter().map(|&(_, n)| n).sum());
+        for &(byte, n) in pairs {
+            bytes.extend(std::iter::repeat_n(byte, n));
+        }
+        bytes
+    }
+
+    /// Shifts the bytes left by `bits` bits, treating the vector as one big-endian bit string.
+    /// Bits shifted off the front are discarded and zeros are shifted in at the back, so the length is unchanged.
+    pub fn shift_bits_left(&mut self, bits: usize) {
+        let (byte_shift, bit_shift) = (bits / 8, bits % 8);
+        let bytes = self.as_slice_mut();
+        for i in 0..bytes.len() {
+            let byte_at = |j: usize| bytes.get(j).copied().unwrap_or(0);
+            let src = i.saturating_add(byte_shift);
+            let mut shifted = byte_at(src) << bit_shift;
+            if bit_shift != 0 {
+                shifted |= byte_at(src.saturating_add(1)) >> (8 - bit_shift);
+            }
+            bytes[i] = shifted;
+        }
+    }
+
+    /// Returns the Shannon entropy of the bytes in the vector, in bits per byte.
+    /// This ranges from 0.0 for a single repeated byte (or an empty vector) to 8.0 for uniformly distributed bytes.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn shannon_entropy(&self) -> f64 {
+        let mut counts = [0usize; 256];
+        for &byte in self.as_slice() {
+            counts[usize::from(byte)] += 1;
+        }
+        let len = self.len as f64;
+        counts
+            .iter()
+            .filter(|&&n| n != 0)
+            .map(|&n| {
+                let p = n as f64 / len;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Returns the first `N` bytes of the vector as an array, or `None` if it's shorter than `N` bytes.
+    #[must_use]
+    pub fn read_header<const N: usize>(&self) -> Option<&[u8; N]> {
+        self.as_slice().first_chunk()
+    }
+
+    /// Returns a polynomial rolling hash of every `window`-byte substring, front to back, as used by Rabin-Karp search.
+    /// Each hash after the first is computed from the previous one in O(1).
+    /// # Panics
+    /// Panics if `window` is zero.
+    #[must_use]
+    pub fn rolling_hashes(&self, window: usize) -> Vec<u64> {
+        const BASE: u64 = 257;
+        assert!(window != 0, "window size must be non-zero");
+        let bytes = self.as_slice();
+        let mut hashes = Vec::with_capacity((bytes.len() + 1).saturating_sub(window));
+        let Some(first) = bytes.get(..window) else {
+            return hashes;
+        };
+
+        // The weight of the oldest byte in the window, which is removed when the window slides.
+        let top = (1..window).fold(1u64, |acc, _| acc.wrapping_mul(BASE));
+        let mut hash = first.iter().fold(0u64, |acc, &b| {
+            acc.wrapping_mul(BASE).wrapping_add(u64::from(b))
+        });
+        hashes.push(hash);
+        for (&old, &new) in bytes.iter().zip(&bytes[window..]) {
+            hash = hash
+                .wrapping_sub(u64::from(old).wrapping_mul(top))
+                .wrapping_mul(BASE)
+                .wrapping_add(u64::from(new));
+            hashes.push(hash);
+        }
+        hashes
+    }
+
+    /// Returns the bytes as a frame, prefixed with their length as a 4-byte big-endian integer.
+    /// # Panics
+    /// Panics if the vector holds more than `u32::MAX` bytes.
+    #[must_use]
+    pub fn to_framed(&self) -> Self {
+        let len = u32::try_from(self.len).expect("payload too long to frame");
+        let mut framed = Self::with_capacity(self.len + 4);
+        framed.extend(len.to_be_bytes().into_iter());
+        framed.extend(self.as_slice().iter().copied());
+        framed
+    }
+
+    /// Parses one frame written by `to_framed` from the start of `bytes`.
+    /// Returns the payload, and the number of bytes the whole frame took up.
+    /// # Errors
+    /// Returns an error if `bytes` is too short to hold the length prefix or the payload.
+    pub fn from_framed(bytes: &[u8]) -> Result<(Self, usize), FrameError> {
+        let (&prefix, rest) = bytes
+            .split_first_chunk::<4>()
+            .ok_or(FrameError::MissingLength {
+                available: bytes.len(),
+            })?;
+        let expected = u32::from_be_bytes(prefix) as usize;
+        let payload = rest.get(..expected).ok_or(FrameError::Truncated {
+            expected,
+            available: rest.len(),
+        })?;
+        let mut v = Self::with_capacity(expected);
+        v.extend(payload.iter().copied());
+        Ok((v, expected + 4))
+    }
+
+    /// Whether the bytes in the vector are valid UTF-8.
+    #[must_use]
+    pub fn is_utf8(&self) -> bool {
+        self.as_str().is_some()
+    }
+
+    /// Returns the bytes in the vector as a string slice, or `None` if they aren't valid UTF-8.
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        std::str::from_utf8(self.as_slice()).ok()
+    }
+
+    /// Sorts the bytes in O(n) time by counting how many times each byte value appears.
+    pub fn counting_sort(&mut self) {
+        let mut counts = [0usize; 256];
+        for &byte in self.as_slice() {
+            counts[usize::from(byte)] += 1;
+        }
+        let mut rest = self.as_slice_mut();
+        for (byte, &n) in (0..=u8::MAX).zip(&counts) {
+            let (run, tail) = rest.split_at_mut(n);
+            run.fill(byte);
+            rest = tail;
+        }
+    }
+
+    /// Packs each group of 4 bytes into a little-endian `u32`.
+    /// Returns `None` if the length isn't a multiple of 4.
+    #[must_use]
+    pub fn pack_u32_le(&self) -> Option<Vec<u32>> {
+        let (words, []) = self.as_slice().as_chunks::<4>() else {
+            return None;
+        };
+        let mut packed = Vec::with_capacity(words.len());
+        packed.extend(words.iter().map(|&w| u32::from_le_bytes(w)));
+        Some(packed)
+    }
+
+    /// Appends formatted text to the vector as UTF-8 bytes, without building an intermediate `String`.
+    /// # Errors
+    /// Returns an error if one of the formatted values' `Display` (or similar) implementation fails.
+    pub fn write_fmt_bytes(&mut self, args: std::fmt::Arguments) -> std::fmt::Result {
+        std::fmt::Write::write_fmt(self, args)
+    }
+
+    /// Reserves enough capacity to append `value` once it's serialized, going by its `SizeHint`.
+    pub fn reserve_for_serialization<S: SizeHint + ?Sized>(&mut self, value: &S) {
+        self.reserve(value.serialized_size());
+    }
+
+    /// Iterates over every contiguous `n`-byte window of the vector, front to back.
+    /// # Panics
+    /// Panics if `n` is zero.
+    pub fn ngrams(&self, n: usize) -> impl Iterator<Item = &[u8]> {
+        assert!(n != 0, "n-gram size must be non-zero");
+        self.as_slice().windows(n)
+    }
+
+    /// Reverses the bytes within each segment delimited by `delim`, keeping the segments and delimiters in place.
+    pub fn reverse_segments(&mut self, delim: u8) {
+        for segment in self.as_slice_mut().split_mut(|&b| b == delim) {
+            segment.reverse();
+        }
+    }
+
+    /// Returns the XOR of every byte, as used for the checksums of protocols like NMEA 0183.
+    /// The checksum of an empty vector is 0.
+    #[must_use]
+    pub fn xor_checksum(&self) -> u8 {
+        self.fold_xor().unwrap_or(0)
+    }
+
+    /// Appends `pad` bytes until the length of the vector is a multiple of `alignment`.
+    /// # Panics
+    /// Panics if `alignment` is zero.
+    pub fn align_to_boundary(&mut self, alignment: usize, pad: u8) {
+        assert!(alignment != 0, "alignment must be non-zero");
+        self.pad_to(self.len.next_multiple_of(alignment), pad);
+    }
+
+    /// Packs `bits` into bytes, most significant bit first, padding the last byte with zeros.
+    pub fn from_bits<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        let bits = bits.into_iter();
+        let mut bytes = Self::with_capacity(bits.size_hint().0.div_ceil(8));
+        let (mut byte, mut filled) = (0u8, 0);
+        for bit in bits {
+            byte |= u8::from(bit) << (7 - filled);
+            filled += 1;
+            if filled == 8 {
+                bytes.push(byte);
+                (byte, filled) = (0, 0);
+            }
+        }
+        if filled != 0 {
+            bytes.push(byte);
+        }
+        bytes
+    }
+
+    /// Unpacks every byte into its bits, most significant bit first.
+    /// This is the inverse of `from_bits`, except that any padding bits are included.
+    #[must_use]
+    pub fn to_bits(&self) -> Vec<bool> {
+        let mut bits = Vec::with_capacity(self.len * 8);
+        bits.extend(
+            self.as_slice()
+                .iter()
+                .flat_map(|&byte| (0..8).map(move |i| byte & (0x80 >> i) != 0)),
+        );
+        bits
+    }
+}
+
+impl std::fmt::Write for Vec<u8> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.extend(s.bytes());
+        Ok(())
+    }
+}
+
+impl Vec<usize> {
+    /// Returns the running offsets of `counts`, where each offset is the sum of all the counts before it.
+    #[must_use]
+    pub fn from_counts(counts: &[usize]) -> Self {
+        let mut offsets = Self::with_capacity(counts.len());
+        offsets.extend(counts.iter().scan(0, |total, &n| {
+            let offset = *total;
+            *total += n;
+            Some(offset)
+        }));
+        offsets
+    }
+
+    /// Returns the indices of the set bits in `mask`, in ascending order, where the lowest bit is index 0.
+    #[must_use]
+    pub fn from_bitmask(mask: u64) -> Self {
+        let mut indices = Self::with_capacity(mask.count_ones() as usize);
+        let mut rest = mask;
+        while rest != 0 {
+            indices.push(rest.trailing_zeros() as usize);
+            rest &= rest - 1;
+        }
+        indices
+    }
+
+    /// Returns the smallest value missing from the vector, which must be sorted and hold no duplicates.
+    /// This is a binary search, so it takes O(log n) time.
+    #[must_use]
+    pub fn first_missing(&self) -> usize {
+        // Every value before the first gap is equal to its own index.
+        let values = self.as_slice();
+        let (mut low, mut high) = (0, values.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if values[mid] == mid {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Returns whether the vector is a permutation of `0..len`, with every value in range and none repeated.
+    /// Seen values are tracked in a bitset, so this takes O(n) time.
+    #[must_use]
+    pub fn is_permutation(&self) -> bool {
+        let values = self.as_slice();
+        let mut seen = vec![0u64; values.len().div_ceil(64)];
+        for &v in values {
+            if v >= values.len() {
+                return false;
+            }
+            let (word, bit) = (v / 64, 1 << (v % 64));
+            if seen[word] & bit != 0 {
+                return false;
+            }
+            seen[word] |= bit;
+        }
+        true
+    }
+
+    /// Returns a bitset of `universe_size` bits packed into `u64` words, with the bit for each value set.
+    /// Bit `i` is bit `i % 64` of word `i / 64`.
+    /// # Panics
+    /// Panics if any value isn't less than `universe_size`.
+    #[must_use]
+    pub fn to_bitset(&self, universe_size: usize) -> Vec<u64> {
+        let words = universe_size.div_ceil(64);
+        let mut bitset = Vec::with_capacity(words);
+        bitset.extend(std::iter::repeat_n(0, words));
+        let bits = bitset.as_slice_mut();
+        for &v in self.as_slice() {
+            assert!(
+                v < universe_size,
+                "value (is {v}) should be < universe_size (is {universe_size})"
+            );
+            bits[v / 64] |= 1 << (v % 64);
+        }
+        bitset
+    }
+}
+
+impl Vec<u64> {
+    /// Decodes a sequence of unsigned LEB128 varints.
+    /// # Errors
+    /// Returns an error if the last varint is cut off, or if a varint doesn't fit in a `u64`.
+    pub fn from_varint_bytes(bytes: &[u8]) -> Result<Self, VarintError> {
+        let mut values = Self::default();
+        let mut value = 0u64;
+        let mut shift = 0;
+        for &byte in bytes {
+            let bits = u64::from(byte & 0x7f);
+            if shift >= u64::BITS || (bits << shift) >> shift != bits {
+                return Err(VarintError::Overflow);
+            }
+            value |= bits << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                values.push(value);
+                value = 0;
+                shift = 0;
+            }
+        }
+        if shift == 0 {
+            Ok(values)
+        } else {
+            Err(VarintError::Truncated)
+        }
+    }
+
+    /// Encodes the values in the vector as a sequence of unsigned LEB128 varints.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_varint_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len);
+        for &value in self.as_slice() {
+            let mut rest = value;
+            while rest >= 0x80 {
+                bytes.push((rest & 0x7f) as u8 | 0x80);
+                rest >>= 7;
+            }
+            bytes.push(rest as u8);
+        }
+        bytes
+    }
+}
+
+/// A vector over borrowed, possibly uninitialized memory, like a stack-allocated array.
+/// It never allocates, so it can't grow past the length of the memory it was given.
+pub struct VecView<'a, T> {
+    /// Memory backing the vector.
+    /// Only the first `len` items are initialized.
+    items: &'a mut [MaybeUninit<T>],
+
+    /// Length of the vector.
+    len: usize,
+}
+
+impl<'a, T> VecView<'a, T> {
+    /// Creates an empty vector backed by `items`.
+    /// Anything already in `items` is treated as uninitialized.
+    pub const fn new(items: &'a mut [MaybeUninit<T>]) -> Self {
+        Self { items, len: 0 }
+    }
+
+    /// Pushes an item onto the end of the vector.
+    /// # Errors
+    /// Returns `x` back if the vector is already full.
+    pub fn push(&mut self, x: T) -> Result<(), T> {
+        let Some(slot) = self.items.get_mut(self.len) else {
+            return Err(x);
+        };
+        slot.write(x);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the last item in the vector, if there is one.
+    pub const fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    /// Returns a slice of all the items in the vector.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        let slice = &self.items[..self.len];
+        unsafe { &*(ptr::from_ref::<[MaybeUninit<T>]>(slice) as *const [T]) }
+    }
+
+    /// Capacity of the vector, or the length of the memory backing it.
+    #[must_use]
+    pub const fn cap(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the number of items the vector is currently storing.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the length of the vector is zero.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T> Drop for VecView<'_, T> {
+    fn drop(&mut self) {
+        // Only the first `len` slots are initialized; the rest of the borrowed memory is left alone.
+        let initialized = ptr::from_mut(&mut self.items[..self.len]) as *mut [T];
+        unsafe { ptr::drop_in_place(initialized) };
+    }
+}
+
+impl Vec<Range<usize>> {
+    /// Sorts the ranges by their starts, and merges any that overlap or touch each other.
+    pub fn coalesce_ranges(&mut self) {
+        let ranges = self.as_slice_mut();
+        ranges.sort_unstable_by_key(|r| r.start);
+        let mut merged = 0;
+        for i in 1..ranges.len() {
+            if ranges[i].start <= ranges[merged].end {
+                ranges[merged].end = ranges[merged].end.max(ranges[i].end);
+            } else {
+                merged += 1;
+                ranges.swap(merged, i);
+            }
+        }
+        self.truncate((merged + 1).min(self.len));
+    }
+}
+
+impl Vec<u32> {
+    /// Unpacks each `u32` into 4 little-endian bytes, the inverse of `pack_u32_le`.
+    #[must_use]
+    pub fn unpack_u32_le(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len * 4);
+        bytes.extend(self.as_slice().iter().flat_map(|w| w.to_le_bytes()));
+        bytes
+    }
+
+    /// Groups each 4 bytes of `bytes` into a big-endian `u32`.
+    /// Returns `None` if the length of `bytes` isn't a multiple of 4.
+    #[must_use]
+    pub fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        let (words, []) = bytes.as_chunks::<4>() else {
+            return None;
+        };
+        let mut v = Self::with_capacity(words.len());
+        v.extend(words.iter().map(|&w| u32::from_be_bytes(w)));
+        Some(v)
+    }
+
+    /// Splits each `u32` into 4 big-endian bytes, the inverse of `from_be_bytes`.
+    #[must_use]
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len * 4);
+        bytes.extend(self.as_slice().iter().flat_map(|w| w.to_be_bytes()));
+        bytes
+    }
+}
+
+impl Vec<String> {
+    /// Splits `s` on every `delim` and collects the pieces as owned strings.
+    #[must_use]
+    pub fn from_delimited(s: &str, delim: char) -> Self {
+        let mut pieces = Self::with_capacity(s.matches(delim).count() + 1);
+        pieces.extend(s.split(delim).map(String::from));
+        pieces
+    }
+}
+
+impl Vec<f64> {
+    /// Removes every value that is within `epsilon` of the last value kept before it.
+    pub fn dedup_approx(&mut self, epsilon: f64) {
+        let mut last_kept: Option<f64> = None;
+        self.retain(|&x| {
+            let keep = last_kept.is_none_or(|last| (x - last).abs() > epsilon);
+            if keep {
+                last_kept = Some(x);
+            }
+            keep
+        });
+    }
+}
+
+impl Vec<Vec<u8>> {
+    /// Moves consecutive buffers into groups holding at most `max_bytes` bytes in total.
+    /// A buffer that is larger than `max_bytes` on its own gets a group to itself.
+    #[must_use]
+    pub fn chunk_by_byte_budget(self, max_bytes: usize) -> std::vec::Vec<Self> {
+        let mut groups = std::vec::Vec::new();
+        let mut group = Self::default();
+        let mut group_bytes = 0;
+        for buffer in self.into_items() {
+            if !group.is_empty() && group_bytes + buffer.len() > max_bytes {
+                groups.push(std::mem::take(&mut group));
+                group_bytes = 0;
+            }
+            group_bytes += buffer.len();
+            group.push(buffer);
+        }
+        if !group.is_empty() {
+            groups.push(group);
+        }
+        groups
+    }
+}
+
+impl<A, B> Vec<(A, B)> {
+    /// Pairs up the items of `a` and `b` until either runs out.
+    /// Space is reserved for the smaller of their size hints up front.
+    pub fn zip_collect<IA: IntoIterator<Item = A>, IB: IntoIterator<Item = B>>(
+        a: IA,
+        b: IB,
+    ) -> Self {
+        let zipped = a.into_iter().zip(b);
+        let mut pairs = Self::with_capacity(zipped.size_hint().0);
+        pairs.extend(zipped);
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::MaybeUninit;
+
+    use super::{FrameError, PermError, RangeSwapError, ScatterError, VarintError, Vec, VecView};
+
+    /// Builds a vector containing clones of `items`.
+    fn vec_of<T: Clone>(items: &[T]) -> Vec<T> {
+        let mut v = Vec::default();
+        v.extend(items.iter().cloned());
+        v
+    }
+
+    #[test]
+    fn reverse_chunks_keeps_chunk_contents() {
+        let mut v = vec_of(&[1, 2, 3, 4, 5, 6]);
+        v.reverse_chunks(2);
+        assert_eq!(v.as_slice(), [5, 6, 3, 4, 1, 2]);
+    }
+
+    #[test]
+    fn reverse_chunks_leaves_partial_chunk() {
+        let mut v = vec_of(&[1, 2, 3, 4, 5]);
+        v.reverse_chunks(2);
+        assert_eq!(v.as_slice(), [3, 4, 1, 2, 5]);
+    }
+
+    #[test]
+    fn dedup_global_keeps_first_occurrences() {
+        let mut v = vec_of(&[1, 2, 1, 3, 2, 4]);
+        v.dedup_global();
+        assert_eq!(v.as_slice(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn split_at_predicate_splits_on_first_failure() {
+        let v = vec_of(&[2, 4, 5, 6]);
+        let (evens, rest) = v.split_at_predicate(|x| x % 2 == 0);
+        assert_eq!(evens, [2, 4]);
+        assert_eq!(rest, [5, 6]);
+    }
+
+    #[test]
+    fn merge_sorted_interleaves() {
+        let mut v = vec_of(&[1, 3, 5]);
+        v.merge_sorted(vec_of(&[2, 4, 6]));
+        assert_eq!(v.as_slice(), [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn merge_sorted_leaks_instead_of_duplicating_on_panic() {
+        use std::{cell::RefCell, cmp::Ordering, panic, rc::Rc};
+
+        /// Records its value when dropped, and panics when compared to 3.
+        struct Key(i32, Rc<RefCell<std::vec::Vec<i32>>>);
+        impl Drop for Key {
+            fn drop(&mut self) {
+                self.1.borrow_mut().push(self.0);
+            }
+        }
+        impl PartialEq for Key {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+        impl Eq for Key {}
+        impl PartialOrd for Key {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Key {
+            fn cmp(&self, other: &Self) -> Ordering {
+                assert!(self.0 != 3 && other.0 != 3, "compared 3");
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let dropped = Rc::new(RefCell::new(std::vec::Vec::new()));
+        let mut v = Vec::default();
+        v.extend((1..=5).map(|n| Key(n, Rc::clone(&dropped))));
+        let mut other = Vec::default();
+        other.push(Key(0, Rc::clone(&dropped)));
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| v.merge_sorted(other)));
+        assert!(result.is_err());
+        v.clear();
+        let mut dropped = dropped.borrow().clone();
+        let drops = dropped.len();
+        dropped.sort_unstable();
+        dropped.dedup();
+        assert_eq!(dropped.len(), drops, "an item was dropped twice");
+    }
+
+    #[test]
+    fn counts_tallies_each_value() {
+        let v = vec_of(&['a', 'a', 'b', 'c', 'c', 'c']);
+        let counts = v.counts();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&'a'], 2);
+        assert_eq!(counts[&'b'], 1);
+        assert_eq!(counts[&'c'], 3);
+    }
+
+    #[test]
+    fn clamp_all_clamps_into_range() {
+        let mut v = vec_of(&[-5, 0, 5, 10]);
+        v.clamp_all(0, 8);
+        assert_eq!(v.as_slice(), [0, 0, 5, 8]);
+    }
+
+    #[test]
+    fn longest_run_finds_longest() {
+        assert_eq!(vec_of(&[1, 1, 2, 2, 2, 1]).longest_run(), 3);
+        assert_eq!(Vec::<i32>::default().longest_run(), 0);
+    }
+
+    #[test]
+    fn rotate_to_first_moves_match_to_front() {
+        let mut v = vec_of(&[1, 2, 3, 4]);
+        assert!(v.rotate_to_first(|&x| x == 3));
+        assert_eq!(v.as_slice(), [3, 4, 1, 2]);
+        assert!(!v.rotate_to_first(|&x| x == 5));
+        assert_eq!(v.as_slice(), [3, 4, 1, 2]);
+    }
+
+    #[test]
+    fn rle_round_trips() {
+        let v = vec_of(b"aaabbc");
+        let pairs = v.rle_encode();
+        assert_eq!(pairs.as_slice(), [(b'a', 3), (b'b', 2), (b'c', 1)]);
+        assert_eq!(Vec::rle_decode(pairs.as_slice()).as_slice(), b"aaabbc");
+    }
+
+    #[test]
+    fn split_into_balances_parts() {
+        let v = vec_of(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let parts = v.split_into(3);
+        let lens: std::vec::Vec<usize> = parts.iter().map(Vec::len).collect();
+        assert_eq!(lens, [4, 3, 3]);
+        assert_eq!(parts[0].as_slice(), [0, 1, 2, 3]);
+        assert_eq!(parts[2].as_slice(), [7, 8, 9]);
+    }
+
+    #[test]
+    fn insert_sorted_bounded_keeps_smallest() {
+        let mut v = Vec::default();
+        for x in [5, 1, 8, 3, 9, 2, 7] {
+            v.insert_sorted_bounded(x, 3);
+        }
+        assert_eq!(v.as_slice(), [1, 2, 3]);
+        assert!(!v.insert_sorted_bounded(4, 3));
+    }
+
+    #[test]
+    fn insert_sorted_bounded_with_zero_max_len() {
+        let mut v = vec_of(&[5]);
+        assert!(!v.insert_sorted_bounded(1, 0));
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn zip_map_sums_pairs() {
+        let a = vec_of(&[1, 2, 3]);
+        let b = vec_of(&[10, 20, 30, 40]);
+        assert_eq!(a.zip_map(&b, |x, y| x + y).as_slice(), [11, 22, 33]);
+    }
+
+    #[test]
+    fn shift_bits_left_carries_across_bytes() {
+        let mut v = vec_of(&[0b1010_0001, 0b1100_0011, 0b1111_0000]);
+        v.shift_bits_left(3);
+        assert_eq!(v.as_slice(), [0b0000_1110, 0b0001_1111, 0b1000_0000]);
+        v.shift_bits_left(12);
+        assert_eq!(v.as_slice(), [0b1111_1000, 0, 0]);
+    }
+
+    #[test]
+    fn trim_matches_trims_both_ends() {
+        let mut v = vec_of(&[0, 0, 1, 2, 0]);
+        v.trim_matches(&0);
+        assert_eq!(v.as_slice(), [1, 2]);
+        let mut zeros = vec_of(&[0, 0]);
+        zeros.trim_matches(&0);
+        assert!(zeros.is_empty());
+    }
+
+    #[test]
+    fn iter_enumerated_from_offsets_indices() {
+        let v = vec_of(&['a', 'b', 'c']);
+        let mut iter = v.iter_enumerated_from(100);
+        assert_eq!(iter.next(), Some((100, &'a')));
+        assert_eq!(iter.last(), Some((102, &'c')));
+    }
+
+    #[test]
+    fn common_prefix_len_stops_at_first_difference() {
+        let v = vec_of(&[1, 2, 3, 9]);
+        assert_eq!(v.common_prefix_len(&[1, 2, 4]), 2);
+        assert_eq!(v.common_prefix_len(&[1, 2]), 2);
+    }
+
+    #[test]
+    fn from_counts_is_exclusive_prefix_sum() {
+        assert_eq!(Vec::from_counts(&[2, 3, 0, 1]).as_slice(), [0, 2, 5, 5]);
+    }
+
+    #[test]
+    fn dedup_by_key_global_keeps_first_per_key() {
+        #[derive(Clone)]
+        struct Person {
+            id: u32,
+            name: &'static str,
+        }
+        let mut people = vec_of(&[
+            Person { id: 1, name: "Ada" },
+            Person { id: 2, name: "Bob" },
+            Person { id: 1, name: "Ann" },
+            Person { id: 3, name: "Cy" },
+            Person { id: 2, name: "Ben" },
+        ]);
+        people.dedup_by_key_global(|p| p.id);
+        let names: std::vec::Vec<_> = people.as_slice().iter().map(|p| p.name).collect();
+        assert_eq!(names, ["Ada", "Bob", "Cy"]);
+    }
+
+    #[test]
+    fn extend_while_stops_at_first_failure() {
+        let mut v = vec_of(&[9]);
+        v.extend_while(0.., |&x| x < 5);
+        assert_eq!(v.as_slice(), [9, 0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn reserve_for_ffi_fills_spare_capacity() {
+        let mut v = vec_of(&[1, 2]);
+        unsafe {
+            let p = v.reserve_for_ffi(3);
+            for i in 0..3 {
+                p.add(i).write(10 * (i + 1));
+            }
+            v.set_len(v.len() + 3);
+        }
+        assert_eq!(v.as_slice(), [1, 2, 10, 20, 30]);
+    }
+
+    #[test]
+    fn leak_returns_static_slice() {
+        let table: &'static mut [i32] = vec_of(&[1, 2, 3]).leak();
+        table[1] = 20;
+        assert_eq!(table, [1, 20, 3]);
+    }
+
+    #[test]
+    fn all_equal_empty() {
+        assert!(Vec::<i32>::default().all_equal());
+    }
+
+    #[test]
+    fn all_equal_uniform() {
+        assert!(vec_of(&[7, 7, 7]).all_equal());
+    }
+
+    #[test]
+    fn all_equal_mixed() {
+        assert!(!vec_of(&[7, 7, 8]).all_equal());
+    }
+
+    #[test]
+    fn moving_average_of_complete_windows() {
+        let v = vec_of(&[1, 2, 3, 4, 5]);
+        assert_eq!(v.moving_average(3).as_slice(), [2.0, 3.0, 4.0]);
+        assert!(v.moving_average(6).is_empty());
+    }
+
+    #[test]
+    fn split_every_chunks_with_short_tail() {
+        let v = vec_of(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let chunks: std::vec::Vec<&[i32]> = v.split_every(4).collect();
+        assert_eq!(chunks, [&[0, 1, 2, 3][..], &[4, 5, 6, 7], &[8, 9]]);
+    }
+
+    #[test]
+    #[should_panic = "chunk size must be non-zero"]
+    fn split_every_zero_panics() {
+        let _ = vec_of(&[1]).split_every(0);
+    }
+
+    #[test]
+    fn varints_round_trip() {
+        let v = vec_of(&[1u64, 300, 70000]);
+        let bytes = v.to_varint_bytes();
+        assert_eq!(bytes.as_slice(), [0x01, 0xac, 0x02, 0xf0, 0xa2, 0x04]);
+        let decoded = Vec::from_varint_bytes(bytes.as_slice()).expect("valid varints");
+        assert_eq!(decoded.as_slice(), [1, 300, 70000]);
+    }
+
+    #[test]
+    fn varints_reject_bad_input() {
+        assert_eq!(
+            Vec::from_varint_bytes(&[0xac]).map(|v| v.len()),
+            Err(VarintError::Truncated)
+        );
+        let too_long = [0xff; 10];
+        assert_eq!(
+            Vec::from_varint_bytes(&too_long).map(|v| v.len()),
+            Err(VarintError::Overflow)
+        );
+    }
+
+    #[test]
+    fn indices_where_finds_matches() {
+        let v = vec_of(&[1, 2, 3, 4]);
+        assert_eq!(v.indices_where(|x| x % 2 == 0).as_slice(), [1, 3]);
+    }
+
+    #[test]
+    fn gather_clones_indexed_items() {
+        let v = vec_of(&['a', 'b', 'c']);
+        let gathered = v.gather(&[2, 0, 2]).expect("indices are in bounds");
+        assert_eq!(gathered.as_slice(), ['c', 'a', 'c']);
+        assert!(v.gather(&[0, 3]).is_none());
+    }
+
+    #[test]
+    fn scatter_writes_only_targeted_slots() {
+        let mut v = vec_of(&[0; 5]);
+        v.scatter(&[3, 1], &[30, 10]).expect("valid scatter");
+        assert_eq!(v.as_slice(), [0, 10, 0, 30, 0]);
+        assert_eq!(
+            v.scatter(&[0, 5], &[1, 2]),
+            Err(ScatterError::OutOfBounds { index: 5, len: 5 })
+        );
+        assert_eq!(
+            v.scatter(&[0], &[1, 2]),
+            Err(ScatterError::LengthMismatch {
+                indices: 1,
+                values: 2
+            })
+        );
+        assert_eq!(v.as_slice(), [0, 10, 0, 30, 0]);
+    }
+
+    #[test]
+    fn dot_multiplies_and_sums() {
+        let v = vec_of(&[1, 2, 3]);
+        assert_eq!(v.dot(&[4, 5, 6]), Some(32));
+        assert_eq!(v.dot(&[4, 5]), None);
+    }
+
+    #[test]
+    fn scale_multiplies_each_item() {
+        let mut v = vec_of(&[1.0, 2.0, 3.0]);
+        v.scale(2.0);
+        assert_eq!(v.as_slice(), [2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn running_max_tracks_maximum() {
+        let v = vec_of(&[1, 3, 2, 5, 4]);
+        assert_eq!(v.running_max().as_slice(), [1, 3, 3, 5, 5]);
+    }
+
+    #[test]
+    fn truncate_by_weight_keeps_prefix_within_budget() {
+        let mut chunks = vec_of(&[&b"abc"[..], b"de", b"fghi", b"j"]);
+        chunks.truncate_by_weight(6, |chunk| chunk.len());
+        assert_eq!(chunks.as_slice(), [&b"abc"[..], b"de"]);
+    }
+
+    #[test]
+    fn iter_with_last_flags_only_last() {
+        let v = vec_of(&[1, 2, 3]);
+        let flags: std::vec::Vec<bool> = v.iter_with_last().map(|(_, last)| last).collect();
+        assert_eq!(flags, [false, false, true]);
+    }
+
+    #[test]
+    fn from_generator_stops_at_none() {
+        let mut n = 5;
+        let v = Vec::from_generator(|| {
+            n -= 1;
+            (n > 0).then_some(n)
+        });
+        assert_eq!(v.as_slice(), [4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn argsort_returns_sorting_indices() {
+        assert_eq!(vec_of(&[30, 10, 20]).argsort().as_slice(), [1, 2, 0]);
+        assert_eq!(vec_of(&[2, 1, 2, 1]).argsort().as_slice(), [1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn apply_permutation_reorders() {
+        let mut v = vec_of(&['a', 'b', 'c']);
+        v.apply_permutation(&[2, 0, 1]).expect("valid permutation");
+        assert_eq!(v.as_slice(), ['c', 'a', 'b']);
+        assert_eq!(
+            v.apply_permutation(&[0, 0, 1]),
+            Err(PermError::Duplicate { index: 0 })
+        );
+        assert_eq!(
+            v.apply_permutation(&[0, 3, 1]),
+            Err(PermError::OutOfBounds { index: 3, len: 3 })
+        );
+        assert_eq!(v.as_slice(), ['c', 'a', 'b']);
+    }
+
+    #[test]
+    fn shannon_entropy_bounds() {
+        let mut uniform = Vec::default();
+        uniform.extend(0..=u8::MAX);
+        assert!((uniform.shannon_entropy() - 8.0).abs() < 1e-9);
+        assert!(vec_of(&[7u8; 16]).shannon_entropy().abs() < 1e-9);
+        assert!(Vec::<u8>::default().shannon_entropy().abs() < 1e-9);
+    }
+
+    #[test]
+    fn from_iter_capped_stops_at_max() {
+        let v = Vec::from_iter_capped(0.., 10);
+        assert_eq!(v.as_slice(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        let m = vec_of(&[1, 2, 3, 4, 5, 6]);
+        let t = m.transpose(2, 3).expect("2x3 matrix");
+        assert_eq!(t.as_slice(), [1, 4, 2, 5, 3, 6]);
+        assert!(m.transpose(4, 2).is_none());
+    }
+
+    #[test]
+    fn median_finds_lower_median() {
+        assert_eq!(vec_of(&[7, 1, 5, 3, 9]).median(), Some(&5));
+        assert_eq!(vec_of(&[4, 1, 3, 2]).median(), Some(&2));
+        assert_eq!(Vec::<i32>::default().median(), None);
+    }
+
+    #[test]
+    fn compress_runs_pairs_values_with_lengths() {
+        let mut v = vec_of(&['a', 'a', 'b', 'c', 'c', 'c']);
+        let runs = v.compress_runs();
+        assert_eq!(runs.as_slice(), [('a', 2), ('b', 1), ('c', 3)]);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn prepend_slice_shifts_existing_items() {
+        let mut v = vec_of(&[1, 2, 3]);
+        v.prepend_slice(&[0, 0]);
+        assert_eq!(v.as_slice(), [0, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn push_bounded_evicts_oldest() {
+        let mut window = Vec::default();
+        let evicted: std::vec::Vec<Option<i32>> =
+            (1..=5).map(|x| window.push_bounded(x, 3)).collect();
+        assert_eq!(evicted, [None, None, None, Some(1), Some(2)]);
+        assert_eq!(window.as_slice(), [3, 4, 5]);
+    }
+
+    #[test]
+    fn read_header_borrows_prefix() {
+        let packet = vec_of(b"\x7fELF\x02\x01");
+        assert_eq!(packet.read_header::<4>(), Some(b"\x7fELF"));
+        assert_eq!(packet.read_header::<7>(), None);
+    }
+
+    #[test]
+    fn difference_removes_shared_items() {
+        let v = vec_of(&[1, 2, 3, 4]);
+        assert_eq!(v.difference(&[2, 4]).as_slice(), [1, 3]);
+    }
+
+    #[test]
+    fn intersection_keeps_shared_items_once() {
+        let v = vec_of(&[1, 2, 2, 3]);
+        assert_eq!(v.intersection(&[2, 3, 4]).as_slice(), [2, 3]);
+    }
+
+    #[test]
+    fn extend_with_slice_fn_commits_filled_slots() {
+        let mut v = vec_of(&[1]);
+        unsafe {
+            v.extend_with_slice_fn(4, |spare| {
+                assert_eq!(spare.len(), 4);
+                spare[0].write(2);
+                spare[1].write(3);
+                2
+            });
+        }
+        assert_eq!(v.as_slice(), [1, 2, 3]);
+        assert!(v.spare_capacity() >= 2);
+    }
+
+    #[test]
+    fn peek_at_looks_ahead() {
+        let v = vec_of(&['a', 'b', 'c', 'd']);
+        assert_eq!(v.peek_at(0, 2), Some(&'c'));
+        assert_eq!(v.peek_at(1, 2), Some(&'d'));
+        assert_eq!(v.peek_at(2, 2), None);
+        assert_eq!(v.peek_at(usize::MAX, 2), None);
+    }
+
+    #[test]
+    fn reserve_percent_grows_proportionally() {
+        let mut v = Vec::default();
+        v.extend(0..100);
+        v.reserve_percent(50);
+        assert!(v.spare_capacity() >= 50);
+
+        let mut small = vec_of(&[1, 2, 3]);
+        small.shrink_to_fit();
+        small.reserve_percent(10);
+        assert!(small.spare_capacity() >= 1);
+    }
+
+    #[test]
+    fn invariants_hold_after_mutations() {
+        let mut v = Vec::default();
+        v.assert_invariants();
+        for x in 0..10 {
+            v.push(x);
+            v.assert_invariants();
+        }
+        v.pop();
+        v.assert_invariants();
+        v.insert(3, 100);
+        v.assert_invariants();
+        v.remove(0);
+        v.assert_invariants();
+        v.shrink_to_fit();
+        v.assert_invariants();
+        assert_eq!(v.as_slice(), [1, 2, 100, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn move_matching_into_partitions() {
+        let mut v = vec_of(&[1, 2, 3, 4]);
+        let mut evens = vec_of(&[0]);
+        v.move_matching_into(&mut evens, |x| x % 2 == 0);
+        assert_eq!(v.as_slice(), [1, 3]);
+        assert_eq!(evens.as_slice(), [0, 2, 4]);
+    }
+
+    #[test]
+    fn swap_contents_exchanges_buffers() {
+        let mut a = vec_of(&[1, 2, 3]);
+        a.set_max_capacity(4);
+        let mut b = Vec::with_capacity(10);
+        b.push(9);
+        a.swap_contents(&mut b);
+        assert_eq!((a.as_slice(), a.cap()), (&[9][..], 10));
+        assert_eq!((b.as_slice(), b.cap()), (&[1, 2, 3][..], 4));
+
+        // The ceiling moved to `b` along with the items it was set for.
+        assert_eq!(b.push_capped(4), Ok(()));
+        assert_eq!(b.push_capped(5), Err(5));
+        a.extend(0..20);
+        assert_eq!(a.len(), 21);
+    }
+
+    #[test]
+    fn rolling_hashes_match_equal_windows() {
+        let v = vec_of(b"abcxabc");
+        let hashes = v.rolling_hashes(3);
+        assert_eq!(hashes.len(), v.len() - 3 + 1);
+        assert_eq!(hashes.get(0), hashes.get(4));
+        assert_ne!(hashes.get(0), hashes.get(1));
+    }
+
+    #[test]
+    fn deinterleave_separates_channels() {
+        let samples = vec_of(&["l0", "r0", "l1", "r1"]);
+        let channels = samples.deinterleave(2);
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[0].as_slice(), ["l0", "l1"]);
+        assert_eq!(channels[1].as_slice(), ["r0", "r1"]);
+    }
+
+    #[test]
+    fn reserve_predicted_reallocates_less() {
+        /// Returns how many times the buffer was reallocated while appending bursts, and in total.
+        fn reallocations(predict: bool) -> (usize, usize) {
+            let mut v = Vec::default();
+            let (mut during_bursts, mut total) = (0, 0);
+            for burst in [100, 120, 80, 100, 110, 90, 100, 100] {
+                for x in 0..burst {
+                    let cap = v.cap();
+                    v.push(x);
+                    during_bursts += usize::from(v.cap() != cap);
+                }
+                if predict {
+                    let cap = v.cap();
+                    v.reserve_predicted(burst);
+                    total += usize::from(v.cap() != cap);
+                }
+            }
+            (during_bursts, total + during_bursts)
+        }
+        // Growth is geometric either way, so the prediction can't skip reallocations,
+        // but it moves them out of the bursts and never adds any.
+        let (naive, naive_total) = reallocations(false);
+        let (predicted, predicted_total) = reallocations(true);
+        assert!(predicted < naive, "{predicted} >= {naive}");
+        assert!(
+            predicted_total <= naive_total,
+            "{predicted_total} > {naive_total}"
+        );
+    }
+
+    #[test]
+    fn vec_view_drops_its_items() {
+        use std::rc::Rc;
+        let item = Rc::new(());
+        let mut storage = [const { MaybeUninit::<Rc<()>>::uninit() }; 4];
+        {
+            let mut view = VecView::new(&mut storage);
+            for _ in 0..3 {
+                view.push(Rc::clone(&item)).expect("space left in storage");
+            }
+            drop(view.pop());
+            assert_eq!(Rc::strong_count(&item), 3);
+        }
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[test]
+    fn vec_view_uses_borrowed_storage() {
+        let mut storage = [const { MaybeUninit::<u8>::uninit() }; 16];
+        let mut view = VecView::new(&mut storage);
+        for b in 0..16 {
+            view.push(b).expect("space left in storage");
+        }
+        assert_eq!(view.push(16), Err(16));
+        assert_eq!(view.pop(), Some(15));
+        assert_eq!(view.len(), 15);
+        assert_eq!(view.as_slice(), (0..15).collect::<std::vec::Vec<u8>>());
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let payload = vec_of(b"hello");
+        let framed = payload.to_framed();
+        assert_eq!(framed.as_slice(), b"\0\0\0\x05hello");
+
+        let mut stream = vec_of(framed.as_slice());
+        stream.extend(b"extra".iter().copied());
+        let (parsed, consumed) = Vec::from_framed(stream.as_slice()).expect("complete frame");
+        assert_eq!(parsed.as_slice(), b"hello");
+        assert_eq!(consumed, 9);
+    }
+
+    #[test]
+    fn frames_reject_truncated_input() {
+        assert_eq!(
+            Vec::from_framed(b"\0\0").map(|(v, _)| v.len()),
+            Err(FrameError::MissingLength { available: 2 })
+        );
+        assert_eq!(
+            Vec::from_framed(b"\0\0\0\x05hel").map(|(v, _)| v.len()),
+            Err(FrameError::Truncated {
+                expected: 5,
+                available: 3
+            })
+        );
+    }
+
+    #[test]
+    fn lcs_len_finds_common_subsequence() {
+        let v = vec_of(&[1, 2, 3, 4]);
+        assert_eq!(v.lcs_len(&[2, 4, 3]), 2);
+        assert_eq!(v.lcs_len(&[]), 0);
+    }
+
+    #[test]
+    fn unfold_generates_fibonacci() {
+        let fib = Vec::unfold((0u32, 1u32), |(a, b)| {
+            let next = *a;
+            (*a, *b) = (*b, *a + *b);
+            (next < 50).then_some(next)
+        });
+        assert_eq!(fib.as_slice(), [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    }
+
+    #[test]
+    fn coalesce_ranges_merges_overlaps() {
+        let mut v = vec_of(&[0..3, 2..5, 7..9]);
+        v.coalesce_ranges();
+        assert_eq!(v.as_slice(), [0..5, 7..9]);
+        let mut touching = vec_of(&[5..6, 7..9, 0..5]);
+        touching.coalesce_ranges();
+        assert_eq!(touching.as_slice(), [0..6, 7..9]);
+        let mut empty: Vec<std::ops::Range<usize>> = Vec::default();
+        empty.coalesce_ranges();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn with_stride_spaces_out_values() {
+        let v = Vec::with_stride(3, 2, 7);
+        assert_eq!(v.as_slice(), [7, 0, 7, 0, 7, 0]);
+    }
+
+    #[test]
+    fn select_nth_finds_kth_smallest() {
+        let v = vec_of(&[5, 2, 8, 1, 9]);
+        assert_eq!(v.select_nth(2), Some(&5));
+        assert_eq!(v.select_nth(0), Some(&1));
+        assert_eq!(v.select_nth(5), None);
+    }
+
+    #[test]
+    fn diff_takes_adjacent_differences() {
+        assert_eq!(vec_of(&[1, 3, 6, 10]).diff().as_slice(), [2, 3, 4]);
+        assert!(vec_of(&[1]).diff().is_empty());
+    }
+
+    #[test]
+    fn pad_to_only_grows() {
+        let mut v = vec_of(&[1, 2]);
+        v.pad_to(5, 0);
+        assert_eq!(v.as_slice(), [1, 2, 0, 0, 0]);
+        v.pad_to(3, 9);
+        assert_eq!(v.as_slice(), [1, 2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn swap_byte_order_reverses_bytes() {
+        let mut v = vec_of(&[0x0102u16, 0x0304]);
+        v.swap_byte_order();
+        assert_eq!(v.as_slice(), [0x0201, 0x0403]);
+    }
+
+    #[test]
+    fn from_bitmask_lists_set_bits() {
+        assert_eq!(Vec::from_bitmask(0b1011).as_slice(), [0, 1, 3]);
+        assert!(Vec::from_bitmask(0).is_empty());
+    }
+
+    #[test]
+    fn downsample_takes_every_nth() {
+        let v = vec_of(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(v.downsample(3).as_slice(), [0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn into_halves_splits_at_midpoint() {
+        let (front, back) = vec_of(&[1, 2, 3, 4, 5]).into_halves();
+        assert_eq!(*front, [1, 2]);
+        assert_eq!(*back, [3, 4, 5]);
+    }
+
+    #[test]
+    fn utf8_ascii() {
+        let v = vec_of(b"hello");
+        assert!(v.is_utf8());
+        assert_eq!(v.as_str(), Some("hello"));
+    }
+
+    #[test]
+    fn utf8_multibyte() {
+        let v = vec_of("grüße, 世界".as_bytes());
+        assert_eq!(v.as_str(), Some("grüße, 世界"));
+    }
+
+    #[test]
+    fn utf8_invalid() {
+        let v = vec_of(&[b'a', 0xc3, 0x28]);
+        assert!(!v.is_utf8());
+        assert_eq!(v.as_str(), None);
+    }
+
+    #[test]
+    fn counting_sort_matches_comparison_sort() {
+        let mut v = Vec::default();
+        v.extend((0..1000u32).map(|i| (i * 7919 % 251) as u8));
+        let mut expected = v.as_slice().to_vec();
+        expected.sort_unstable();
+        v.counting_sort();
+        assert_eq!(v.as_slice(), expected);
+    }
+
+    #[test]
+    fn mean_variance_in_one_pass() {
+        let v = vec_of(&[2, 4, 4, 4, 5, 5, 7, 9]);
+        let (mean, variance) = v.mean_variance().expect("non-empty");
+        assert!((mean - 5.0).abs() < 1e-12);
+        assert!((variance - 4.0).abs() < 1e-12);
+        assert!(Vec::<i32>::default().mean_variance().is_none());
+    }
+
+    #[test]
+    fn cap_duplicates_limits_occurrences() {
+        let mut v = vec_of(&['a', 'a', 'a', 'b', 'a']);
+        v.cap_duplicates(2);
+        assert_eq!(v.as_slice(), ['a', 'a', 'b']);
+    }
+
+    #[test]
+    fn sort_indices_by_key_orders_by_age() {
+        let people = vec_of(&[("Ada", 36), ("Bob", 25), ("Cy", 41), ("Di", 25)]);
+        let order = people.sort_indices_by_key(|&(_, age)| age);
+        assert_eq!(order.as_slice(), [1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn u32_le_round_trips() {
+        let bytes = vec_of(&[0x01, 0x02, 0x03, 0x04, 0xff, 0, 0, 0]);
+        let words = bytes.pack_u32_le().expect("multiple of 4 bytes");
+        assert_eq!(words.as_slice(), [0x0403_0201, 0xff]);
+        assert_eq!(words.unpack_u32_le().as_slice(), bytes.as_slice());
+    }
+
+    #[test]
+    fn pack_u32_le_rejects_partial_words() {
+        assert!(vec_of(&[1u8, 2, 3]).pack_u32_le().is_none());
+    }
+
+    #[test]
+    fn runs_yields_ranges_and_values() {
+        let v = vec_of(&['a', 'a', 'b', 'c', 'c']);
+        let runs: std::vec::Vec<_> = v.runs().collect();
+        assert_eq!(runs, [(0..2, &'a'), (2..3, &'b'), (3..5, &'c')]);
+    }
+
+    #[test]
+    fn delete_range_closes_gap() {
+        let mut v = vec_of(&[0, 1, 2, 3, 4, 5, 6]);
+        v.delete_range(2..5);
+        assert_eq!(v.as_slice(), [0, 1, 5, 6]);
+        v.delete_range(..=1);
+        assert_eq!(v.as_slice(), [5, 6]);
+        v.delete_range(..);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[should_panic = "range end (is 3) should be <= len (is 2)"]
+    fn delete_range_out_of_bounds_panics() {
+        vec_of(&[0, 1]).delete_range(1..3);
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn extend_stream_pushes_stream_items() {
+        let mut v = vec_of(&[0]);
+        let mut stream = futures::stream::iter(1..=4);
+        futures::executor::block_on(v.extend_stream(&mut stream));
+        assert_eq!(v.as_slice(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn to_coords_unflattens_index() {
+        let v = vec_of(&[0; 6]);
+        assert_eq!(
+            v.to_coords(5, &[2, 3]).as_ref().map(Vec::as_slice),
+            Some(&[1, 2][..])
+        );
+        assert_eq!(
+            v.to_coords(4, &[3, 2]).as_ref().map(Vec::as_slice),
+            Some(&[2, 0][..])
+        );
+        assert!(v.to_coords(6, &[2, 3]).is_none());
+        assert!(v.to_coords(0, &[2, 2]).is_none());
+    }
+
+    #[test]
+    fn from_delimited_splits_into_strings() {
+        let fields = Vec::from_delimited("a,b,c", ',');
+        assert_eq!(fields.as_slice(), ["a", "b", "c"]);
+        assert_eq!(Vec::from_delimited("a,,", ',').as_slice(), ["a", "", ""]);
+    }
+
+    #[test]
+    fn merge_sort_is_stable() {
+        /// Compares only by `key`, so sorting can't use `payload` to break ties.
+        #[derive(Clone, Debug)]
+        struct Record {
+            key: u8,
+            payload: char,
+        }
+        impl PartialEq for Record {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+        impl Eq for Record {}
+        impl PartialOrd for Record {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Record {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.key.cmp(&other.key)
+            }
+        }
+
+        let mut v = Vec::default();
+        v.extend(
+            [
+                (3, 'a'),
+                (1, 'b'),
+                (3, 'c'),
+                (2, 'd'),
+                (1, 'e'),
+                (3, 'f'),
+                (0, 'g'),
+            ]
+            .into_iter()
+            .map(|(key, payload)| Record { key, payload }),
+        );
+        v.merge_sort();
+        let payloads: String = v.as_slice().iter().map(|r| r.payload).collect();
+        assert_eq!(payloads, "gbedacf");
+    }
+
+    #[test]
+    fn canonical_rotation_picks_smallest() {
+        for rotation in [[3, 1, 2], [2, 3, 1], [1, 2, 3]] {
+            let mut v = vec_of(&rotation);
+            v.canonical_rotation();
+            assert_eq!(v.as_slice(), [1, 2, 3]);
+        }
+        let mut v = vec_of(&[1, 2, 1, 1, 2]);
+        v.canonical_rotation();
+        assert_eq!(v.as_slice(), [1, 1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn abs_diff_scalar_measures_distance() {
+        assert_eq!(
+            vec_of(&[1u32, 5, 3]).abs_diff_scalar(3).as_slice(),
+            [2, 2, 0]
+        );
+        assert_eq!(vec_of(&[-1, 4]).abs_diff_scalar(1).as_slice(), [2, 3]);
+    }
+
+    #[test]
+    fn truncate_at_cuts_before_value() {
+        let mut v = vec_of(&[1, 2, 0, 3]);
+        assert!(v.truncate_at(&0));
+        assert_eq!(v.as_slice(), [1, 2]);
+        assert!(!v.truncate_at(&0));
+        assert_eq!(v.as_slice(), [1, 2]);
+    }
+
+    #[test]
+    fn u32_be_round_trips() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0, 0, 0, 0xff];
+        let words = Vec::from_be_bytes(&bytes).expect("multiple of 4 bytes");
+        assert_eq!(words.as_slice(), [0x0102_0304, 0xff]);
+        assert_eq!(words.to_be_bytes().as_slice(), bytes);
+        assert!(Vec::from_be_bytes(&bytes[..5]).is_none());
+    }
+
+    #[test]
+    fn dedup_approx_merges_close_values() {
+        let mut v = vec_of(&[1.0, 1.000_000_1, 2.0, 2.000_000_1]);
+        v.dedup_approx(1e-6);
+        assert_eq!(v.as_slice(), [1.0, 2.0]);
+    }
+
+    #[test]
+    fn split_by_sizes_cuts_fields() {
+        let v = vec_of(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let fields = v
+            .split_by_sizes(&[3, 5, 2])
+            .expect("sizes add up to the length");
+        assert_eq!(fields, [&[0, 1, 2][..], &[3, 4, 5, 6, 7], &[8, 9]]);
+        assert!(v.split_by_sizes(&[3, 5]).is_none());
+        assert!(v.split_by_sizes(&[3, 8]).is_none());
+    }
+
+    #[test]
+    fn levenshtein_counts_edits() {
+        assert_eq!(vec_of(b"kitten").levenshtein(b"sitting"), 3);
+        assert_eq!(vec_of(b"abc").levenshtein(b""), 3);
+        assert_eq!(vec_of(b"abc").levenshtein(b"abc"), 0);
+    }
+
+    #[test]
+    fn iter_runs_yields_values_and_lengths() {
+        let v = vec_of(&['a', 'a', 'b', 'c', 'c', 'c']);
+        let runs: std::vec::Vec<_> = v.iter_runs().collect();
+        assert_eq!(runs, [(&'a', 2), (&'b', 1), (&'c', 3)]);
+    }
+
+    #[test]
+    fn compare_replace_swaps_on_match() {
+        let mut v = vec_of(&[1, 2, 3]);
+        assert_eq!(v.compare_replace(1, &2, 20), Ok(2));
+        assert_eq!(v.as_slice(), [1, 20, 3]);
+    }
+
+    #[test]
+    fn compare_replace_rejects_mismatch() {
+        let mut v = vec_of(&[1, 2, 3]);
+        assert_eq!(v.compare_replace(1, &5, 20), Err(20));
+        assert_eq!(v.as_slice(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn write_fmt_bytes_appends_text() {
+        use std::fmt::Write;
+
+        let mut v = vec_of(b">");
+        write!(v, "{}-{}", 1, 2).expect("writing to a vector can't fail");
+        v.write_fmt_bytes(format_args!("{:02}", 3))
+            .expect("writing to a vector can't fail");
+        assert_eq!(v.as_slice(), b">1-203");
+    }
+
+    #[test]
+    fn partition_off_moves_matches_out() {
+        let mut v = vec_of(&[1, 2, 3, 4]);
+        let large = v.partition_off(|&x| x > 2);
+        assert_eq!(v.as_slice(), [1, 2]);
+        assert_eq!(large.as_slice(), [3, 4]);
+    }
+
+    #[test]
+    fn weighted_sum_weights_items() {
+        let v = vec_of(&[1, 2, 3]);
+        assert_eq!(v.weighted_sum(&[0, 1, 0]), Some(2));
+        assert_eq!(v.weighted_sum(&[0, 1]), None);
+    }
+
+    #[test]
+    fn range_map_maps_indices() {
+        let v = Vec::range_map(10..13, |i| i);
+        assert_eq!(v.as_slice(), [10, 11, 12]);
+        assert_eq!(v.cap(), 3);
+    }
+
+    #[test]
+    fn fold_and_intersects_bits() {
+        assert_eq!(vec_of(&[0b1110u8, 0b0111, 0b0110]).fold_and(), Some(0b0110));
+        assert_eq!(Vec::<u8>::default().fold_and(), None);
+    }
+
+    #[test]
+    fn fold_or_unions_bits() {
+        assert_eq!(vec_of(&[0b0001u8, 0b0100, 0b0101]).fold_or(), Some(0b0101));
+    }
+
+    #[test]
+    fn fold_xor_toggles_bits() {
+        assert_eq!(vec_of(&[0b0011u8, 0b0110, 0b0001]).fold_xor(), Some(0b0100));
+    }
+
+    #[test]
+    fn rotate_wraps_signed_offsets() {
+        let mut v = vec_of(&[1, 2, 3, 4]);
+        v.rotate(-1);
+        assert_eq!(v.as_slice(), [4, 1, 2, 3]);
+        let mut v = vec_of(&[1, 2, 3, 4]);
+        v.rotate(5);
+        assert_eq!(v.as_slice(), [2, 3, 4, 1]);
+        Vec::<i32>::default().rotate(3);
+    }
+
+    #[test]
+    fn join_with_inserts_separators() {
+        let parts: [&[i32]; 3] = [&[1, 2], &[3], &[4, 5]];
+        let joined = Vec::join_with(parts, &[0]);
+        assert_eq!(joined.as_slice(), [1, 2, 0, 3, 0, 4, 5]);
+        assert_eq!(joined.cap(), 7);
+    }
+
+    #[test]
+    fn histogram_bins_and_clamps() {
+        let v = vec_of(&[-3.0, 0.0, 1.0, 2.4, 2.6, 5.0, 9.9, 10.0, 42.0]);
+        assert_eq!(v.histogram(0.0, 10.0, 4).as_slice(), [4, 1, 1, 3]);
+    }
+
+    #[test]
+    fn replace_range_with_collapses_range() {
+        let mut v = vec_of(&[0, 1, 2, 3, 4, 5, 6]);
+        v.replace_range_with(2..5, 9);
+        assert_eq!(v.as_slice(), [0, 1, 9, 5, 6]);
+        v.replace_range_with(1..1, 8);
+        assert_eq!(v.as_slice(), [0, 8, 1, 9, 5, 6]);
+    }
+
+    #[test]
+    fn reserve_for_serialization_meets_hint() {
+        let mut buf = vec_of(b"header");
+        let values = [1u64, 2, 3];
+        buf.reserve_for_serialization(&values[..]);
+        assert!(buf.spare_capacity() >= 24);
+        buf.reserve_for_serialization("héllo, world, this is a longer string");
+        assert!(buf.spare_capacity() >= 38);
+    }
+
+    #[test]
+    fn first_missing_finds_gap() {
+        assert_eq!(vec_of(&[0, 1, 2, 4, 5]).first_missing(), 3);
+        assert_eq!(vec_of(&[0, 1, 2]).first_missing(), 3);
+        assert_eq!(vec_of(&[1, 2]).first_missing(), 0);
+    }
+
+    #[test]
+    fn ngrams_yields_windows() {
+        let v = vec_of(b"abc");
+        let bigrams: std::vec::Vec<&[u8]> = v.ngrams(2).collect();
+        assert_eq!(bigrams, [b"ab", b"bc"]);
+    }
+
+    #[test]
+    fn swap_ranges_exchanges_contents() {
+        let mut v = vec_of(&[1, 2, 3, 4, 5]);
+        v.swap_ranges(0..2, 3..5)
+            .expect("disjoint ranges of equal length");
+        assert_eq!(v.as_slice(), [4, 5, 3, 1, 2]);
+    }
+
+    #[test]
+    fn swap_ranges_rejects_invalid_ranges() {
+        let mut v = vec_of(&[1, 2, 3, 4, 5]);
+        assert_eq!(v.swap_ranges(0..2, 1..3), Err(RangeSwapError::Overlap));
+        assert_eq!(
+            v.swap_ranges(0..2, 2..5),
+            Err(RangeSwapError::LengthMismatch { a: 2, b: 3 })
+        );
+        assert_eq!(
+            v.swap_ranges(0..2, 4..6),
+            Err(RangeSwapError::OutOfBounds {
+                range: 4..6,
+                len: 5
+            })
+        );
+        assert_eq!(v.as_slice(), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn suffix_sum_accumulates_from_back() {
+        let mut v = vec_of(&[1, 2, 3, 4]);
+        v.suffix_sum();
+        assert_eq!(v.as_slice(), [10, 9, 7, 4]);
+    }
+
+    #[test]
+    fn chunk_by_byte_budget_groups_buffers() {
+        let mut buffers = Vec::default();
+        for b in [&b"abc"[..], b"de", b"fghijklm", b"n", b"op", b"q"] {
+            buffers.push(vec_of(b));
+        }
+        let groups = buffers.chunk_by_byte_budget(5);
+        let sizes: std::vec::Vec<std::vec::Vec<usize>> = groups
+            .iter()
+            .map(|g| g.as_slice().iter().map(Vec::len).collect())
+            .collect();
+        assert_eq!(sizes, [vec![3, 2], vec![8], vec![1, 2, 1]]);
+    }
+
+    #[test]
+    fn convolve_full_length() {
+        let signal = vec_of(&[1.0_f64, 2.0, 3.0]);
+        let out = signal.convolve(&[0.0, 1.0, 0.5]);
+        let expected = [0.0, 1.0, 2.5, 4.0, 1.5];
+        assert_eq!(out.len(), expected.len());
+        for (a, b) in out.as_slice().iter().zip(expected) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert!(signal.convolve(&[]).is_empty());
+    }
+
+    #[test]
+    fn is_permutation_checks_range_and_duplicates() {
+        assert!(vec_of(&[2usize, 0, 3, 1]).is_permutation());
+        assert!(Vec::<usize>::default().is_permutation());
+        assert!(!vec_of(&[0usize, 1, 1, 3]).is_permutation());
+        assert!(!vec_of(&[0usize, 1, 4]).is_permutation());
+    }
+
+    #[test]
+    fn from_runs_expands_pairs() {
+        let v = Vec::from_runs(&[('a', 2), ('b', 3), ('c', 0)]);
+        assert_eq!(v.as_slice(), ['a', 'a', 'b', 'b', 'b']);
+        assert_eq!(v.cap(), 5);
+    }
+
+    #[test]
+    fn mode_returns_most_frequent() {
+        assert_eq!(vec_of(&[1, 2, 2, 3, 3, 3]).mode(), Some(&3));
+        assert_eq!(vec_of(&['a', 'b', 'b', 'a']).mode(), Some(&'a'));
+        assert_eq!(Vec::<i32>::default().mode(), None);
+    }
+
+    #[test]
+    fn try_extend_with_keeps_partial_progress() {
+        let mut v = vec_of(&[0]);
+        let mut n = 0;
+        let result = v.try_extend_with(|| {
+            n += 1;
+            if n <= 3 {
+                Ok(Some(n))
+            } else {
+                Err("out of values")
+            }
+        });
+        assert_eq!(result, Err("out of values"));
+        assert_eq!(v.as_slice(), [0, 1, 2, 3]);
+
+        let mut rest = [5, 6].into_iter();
+        assert_eq!(v.try_extend_with(|| Ok::<_, ()>(rest.next())), Ok(()));
+        assert_eq!(v.as_slice(), [0, 1, 2, 3, 5, 6]);
+    }
+
+    #[test]
+    fn remove_indices_returns_removed_in_order() {
+        let mut v = vec_of(&['a', 'b', 'c', 'd', 'e']);
+        let removed = v.remove_indices(&[3, 1]);
+        assert_eq!(removed.as_slice(), ['b', 'd']);
+        assert_eq!(v.as_slice(), ['a', 'c', 'e']);
+    }
+
+    #[test]
+    #[should_panic = "index 0 is given more than once"]
+    fn remove_indices_rejects_duplicates() {
+        let _ = vec_of(&[1, 2, 3]).remove_indices(&[0, 0]);
+    }
+
+    #[test]
+    fn sliding_max_over_windows() {
+        let v = vec_of(&[1, 3, 2, 5, 4]);
+        assert_eq!(v.sliding_max(3).as_slice(), [3, 5, 5]);
+        assert_eq!(v.sliding_max(1).as_slice(), v.as_slice());
+        assert_eq!(v.sliding_max(5).as_slice(), [5]);
+    }
+
+    #[test]
+    #[should_panic = "window (is 6) should be <= len (is 5)"]
+    fn sliding_max_rejects_oversized_window() {
+        let _ = vec_of(&[1, 3, 2, 5, 4]).sliding_max(6);
+    }
+
+    #[test]
+    fn peek_top_returns_last_items() {
+        let stack = vec_of(&[1, 2, 3, 4, 5]);
+        assert_eq!(stack.peek_top(2), [4, 5]);
+        assert_eq!(stack.peek_top(0), [] as [i32; 0]);
+        assert_eq!(stack.peek_top(9), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn shift_left_fills_tail_with_default() {
+        let mut v = vec_of(&[1, 2, 3, 4]);
+        v.shift_left(2);
+        assert_eq!(v.as_slice(), [3, 4, 0, 0]);
+        v.shift_left(10);
+        assert_eq!(v.as_slice(), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn jaccard_similarity() {
+        assert!((vec_of(&[1, 2, 3]).jaccard(&[2, 3, 4]) - 0.5).abs() < 1e-12);
+        assert!((vec_of(&[1, 1, 2]).jaccard(&[2, 1]) - 1.0).abs() < 1e-12);
+        assert!((Vec::<i32>::default().jaccard(&[]) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn reverse_segments_keeps_delimiters() {
+        let mut v = vec_of(b"ab,cd,ef");
+        v.reverse_segments(b',');
+        assert_eq!(v.as_slice(), b"ba,dc,fe");
+        let mut v = vec_of(b",abc,,");
+        v.reverse_segments(b',');
+        assert_eq!(v.as_slice(), b",cba,,");
+    }
+
+    #[test]
+    fn push_capped_respects_max_capacity() {
+        let mut v = Vec::default();
+        v.set_max_capacity(4);
+        for i in 0..4 {
+            assert_eq!(v.push_capped(i), Ok(()));
+        }
+        assert_eq!(v.push_capped(4), Err(4));
+        assert_eq!(v.as_slice(), [0, 1, 2, 3]);
+        assert!(v.cap() <= 4);
+    }
+
+    #[test]
+    fn swap_contents_moves_max_capacity_with_buffer() {
+        let mut capped = Vec::default();
+        capped.set_max_capacity(4);
+        capped.extend(0..3);
+        let mut big = Vec::with_capacity(16);
+        big.extend(10..12);
+
+        capped.swap_contents(&mut big);
+        assert_eq!(capped.as_slice(), [10, 11]);
+        assert_eq!(capped.cap(), 16);
+        capped.extend(12..40);
+        assert_eq!(capped.len(), 30);
+
+        assert_eq!(big.as_slice(), [0, 1, 2]);
+        assert_eq!(big.push_capped(3), Ok(()));
+        assert_eq!(big.push_capped(4), Err(4));
+        assert!(big.cap() <= 4);
+    }
+
+    #[test]
+    fn reservations_are_clamped_to_max_capacity() {
+        let mut v = Vec::default();
+        v.set_max_capacity(4);
+        v.extend(0..3);
+        v.reserve_percent(100);
+        v.reserve_predicted(10);
+        v.reserve(100);
+        assert_eq!(v.cap(), 4);
+        assert_eq!(v.push_capped(3), Ok(()));
+        assert_eq!(v.push_capped(4), Err(4));
+    }
+
+    #[test]
+    fn push_capped_fills_a_buffer_larger_than_the_ceiling() {
+        let mut v = Vec::with_capacity(8);
+        v.set_max_capacity(4);
+        for i in 0..8 {
+            assert_eq!(v.push_capped(i), Ok(()));
+        }
+        assert_eq!(v.push_capped(8), Err(8));
+        assert_eq!(v.cap(), 8);
+    }
+
+    #[test]
+    fn max_capacity_clamps_growth() {
+        let mut v = Vec::default();
+        v.set_max_capacity(5);
+        v.extend(0..5);
+        assert_eq!(v.cap(), 5);
+    }
+
+    #[test]
+    #[should_panic = "required capacity (is 6) should be <= max capacity (is 5)"]
+    fn push_past_max_capacity_panics() {
+        let mut v = Vec::default();
+        v.set_max_capacity(5);
+        v.extend(0..6);
+    }
+
+    #[test]
+    fn equal_neighbors_mask_marks_boundaries() {
+        let mask = vec_of(&[1, 1, 2, 2, 3]).equal_neighbors_mask();
+        assert_eq!(mask.as_slice(), [true, false, true, false]);
+        assert!(vec_of(&[1]).equal_neighbors_mask().is_empty());
+    }
+
+    #[test]
+    fn extend_unique_skips_present_items() {
+        let mut v = vec_of(&[1, 2]);
+        v.extend_unique([2, 3, 3, 4]);
+        assert_eq!(v.as_slice(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn bucket_offsets_prefix_sums() {
+        let v = vec_of(&[0usize, 1, 1, 2]);
+        assert_eq!(v.bucket_offsets(3, |&x| x).as_slice(), [0, 1, 3, 4]);
+        assert_eq!(v.bucket_offsets(4, |&x| 3 - x).as_slice(), [0, 0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn sorted_unique_copies_distinct_items() {
+        let v = vec_of(&[3, 1, 2, 1, 3]);
+        assert_eq!(v.sorted_unique().as_slice(), [1, 2, 3]);
+        assert_eq!(v.as_slice(), [3, 1, 2, 1, 3]);
+    }
+
+    #[test]
+    fn xor_checksum_of_bytes() {
+        assert_eq!(vec_of(&[0x01, 0x02, 0x04, 0xff]).xor_checksum(), 0xf8);
+        assert_eq!(vec_of(b"AB").xor_checksum(), 0x03);
+        assert_eq!(Vec::<u8>::default().xor_checksum(), 0);
+    }
+
+    #[test]
+    fn round_robin_next_cycles() {
+        let mut v = vec_of(&['a', 'b', 'c']);
+        let order: std::vec::Vec<char> = (0..5)
+            .filter_map(|_| v.round_robin_next().copied())
+            .collect();
+        assert_eq!(order, ['a', 'b', 'c', 'a', 'b']);
+        assert_eq!(Vec::<char>::default().round_robin_next(), None);
+    }
+
+    #[test]
+    fn truncate_to_capacity_fraction_bounds_len() {
+        let mut v = Vec::with_capacity(8);
+        v.extend(0..8);
+        v.truncate_to_capacity_fraction(0.7);
+        assert_eq!(v.as_slice(), [0, 1, 2, 3, 4]);
+        v.truncate_to_capacity_fraction(0.9);
+        assert_eq!(v.len(), 5);
+        v.truncate_to_capacity_fraction(-1.0);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn zip_collect_stops_at_shorter() {
+        let pairs = Vec::zip_collect([1, 2, 3], ["a", "b"]);
+        assert_eq!(pairs.as_slice(), [(1, "a"), (2, "b")]);
+        assert_eq!(pairs.cap(), 2);
+    }
+
+    #[test]
+    fn contains_subsequence_respects_order() {
+        let v = vec_of(&[1, 2, 3]);
+        assert!(v.contains_subsequence(&[1, 3]));
+        assert!(v.contains_subsequence(&[]));
+        assert!(!v.contains_subsequence(&[3, 1]));
+        assert!(!v.contains_subsequence(&[1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn percentile_interpolates() {
+        let v = vec_of(&[4u8, 1, 3, 2]);
+        assert!((v.percentile(50.0).expect("non-empty") - 2.5).abs() < 1e-12);
+        assert!((v.percentile(0.0).expect("non-empty") - 1.0).abs() < 1e-12);
+        assert!((v.percentile(100.0).expect("non-empty") - 4.0).abs() < 1e-12);
+        assert_eq!(Vec::<u8>::default().percentile(50.0), None);
+    }
+
+    #[test]
+    fn split_owned_moves_pieces() {
+        let pieces = vec_of(&[1, 0, 2, 3, 0, 4]).split_owned(|&x| x == 0);
+        let pieces: std::vec::Vec<&[i32]> = pieces.iter().map(Vec::as_slice).collect();
+        assert_eq!(pieces, [&[1][..], &[2, 3], &[4]]);
+
+        let pieces = vec_of(&[0, 1, 0, 0]).split_owned(|&x| x == 0);
+        assert_eq!(
+            pieces.iter().map(Vec::len).collect::<std::vec::Vec<_>>(),
+            [0, 1, 0, 0]
+        );
+    }
+
+    #[test]
+    fn to_bitset_sets_bits() {
+        let bitset = vec_of(&[0usize, 3, 64]).to_bitset(128);
+        assert_eq!(bitset.as_slice(), [0b1001, 1]);
+        assert_eq!(vec_of(&[4usize]).to_bitset(65).len(), 2);
+    }
+
+    #[test]
+    #[should_panic = "value (is 128) should be < universe_size (is 128)"]
+    fn to_bitset_rejects_values_outside_universe() {
+        let _ = vec_of(&[128usize]).to_bitset(128);
+    }
+
+    #[test]
+    fn insertion_sort_matches_std_sort() {
+        let items = [1, 2, 4, 3, 5, 6, 8, 7, 9, 0];
+        let mut v = vec_of(&items);
+        v.insertion_sort();
+        let mut expected = items.to_vec();
+        expected.sort_unstable();
+        assert_eq!(v.as_slice(), expected);
+    }
+
+    #[test]
+    fn lis_len_of_sequence() {
+        assert_eq!(vec_of(&[3, 1, 4, 1, 5, 9, 2, 6]).lis_len(), 4);
+        assert_eq!(vec_of(&[2, 2, 2]).lis_len(), 1);
+        assert_eq!(Vec::<i32>::default().lis_len(), 0);
+    }
+
+    #[test]
+    fn align_to_boundary_pads_bytes() {
+        let mut v = vec_of(&[1, 2, 3, 4, 5]);
+        v.align_to_boundary(8, 0);
+        assert_eq!(v.as_slice(), [1, 2, 3, 4, 5, 0, 0, 0]);
+        v.align_to_boundary(4, 0xff);
+        assert_eq!(v.len(), 8);
+    }
+
+    #[test]
+    fn pairs_mut_swaps_each_pair() {
+        let mut v = vec_of(&[1, 2, 3, 4, 5]);
+        for (a, b) in v.pairs_mut() {
+            std::mem::swap(a, b);
+        }
+        assert_eq!(v.as_slice(), [2, 1, 4, 3, 5]);
+    }
+
+    #[test]
+    fn by_frequency_sorts_by_count() {
+        let v = vec_of(&['a', 'b', 'a', 'c', 'a', 'b', 'd']);
+        assert_eq!(
+            v.by_frequency().as_slice(),
+            [('a', 3), ('b', 2), ('c', 1), ('d', 1)]
+        );
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    fn repair_clamps_len_to_cap() {
+        let mut v = Vec::with_capacity(4);
+        v.extend(0..4);
+        // Deliberately break the length invariant; nothing reads the items until it's repaired.
+        unsafe { v.set_len(v.cap() + 3) };
+        assert!(v.repair());
+        assert_eq!(v.len(), v.cap());
+        assert_eq!(v.as_slice(), [0, 1, 2, 3]);
+        assert!(!v.repair());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn reservoir_sample_draws_from_source() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let v = vec_of(&[10, 20, 30, 40, 50, 60, 70, 80]);
+        for k in [0, 3, 8, 20] {
+            let sample = v.reservoir_sample(k, &mut rng);
+            assert_eq!(sample.len(), k.min(v.len()));
+            assert!(sample.as_slice().iter().all(|x| v.as_slice().contains(x)));
+        }
+    }
+
+    #[test]
+    fn shift_right_fills_head_with_default() {
+        let mut v = vec_of(&[1, 2, 3, 4]);
+        v.shift_right(2);
+        assert_eq!(v.as_slice(), [0, 0, 1, 2]);
+        v.shift_right(10);
+        assert_eq!(v.as_slice(), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn zip_min_and_zip_max() {
+        let v = vec_of(&[1, 5, 3]);
+        assert_eq!(v.zip_max(&[4, 2, 6]).as_slice(), [4, 5, 6]);
+        assert_eq!(v.zip_min(&[4, 2, 6]).as_slice(), [1, 2, 3]);
+        assert_eq!(v.zip_max(&[0, 9]).as_slice(), [1, 9]);
+    }
+
+    #[test]
+    fn bits_round_trip() {
+        let bits = [
+            true, false, true, true, false, false, false, true, true, true,
+        ];
+        let bytes = Vec::from_bits(bits);
+        assert_eq!(bytes.as_slice(), [0b1011_0001, 0b1100_0000]);
+        let unpacked = bytes.to_bits();
+        assert_eq!(&unpacked.as_slice()[..bits.len()], bits);
+        assert!(unpacked.as_slice()[bits.len()..].iter().all(|&bit| !bit));
+    }
+
+    #[test]
+    fn nearest_finds_closest_index() {
+        assert_eq!(vec_of(&[1u32, 3, 8]).nearest(&4), Some(1));
+        assert_eq!(vec_of(&[2.0, 6.0]).nearest(&4.0), Some(0));
+        assert_eq!(Vec::<u32>::default().nearest(&4), None);
+    }
+
+    #[test]
+    fn retain_tail_by_weight_keeps_recent_entries() {
+        let mut log = vec_of(&["boot", "connect", "request", "ok", "bye"]);
+        log.retain_tail_by_weight(12, |entry| entry.len());
+        assert_eq!(log.as_slice(), ["request", "ok", "bye"]);
+        log.retain_tail_by_weight(0, |entry| entry.len());
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn sort_by_key_gather_calls_key_once_per_item() {
+        let mut records = vec_of(&[("carol", 35), ("alice", 30), ("bob", 35), ("dave", 25)]);
+        let mut calls = 0;
+        records.sort_by_key_gather(|&(_, age)| {
+            calls += 1;
+            age
+        });
+        assert_eq!(calls, 4);
+        assert_eq!(
+            records.as_slice(),
+            [("dave", 25), ("alice", 30), ("carol", 35), ("bob", 35)]
+        );
+    }
+
+    #[test]
+    fn flat_map_into_flattens_outputs() {
+        let v = vec_of(&[1, 2]);
+        assert_eq!(v.flat_map_into(|x| [x, x * 10]).as_slice(), [1, 10, 2, 20]);
+        let words = vec_of(&[String::from("ab"), String::from("c")]);
+        assert_eq!(words.flat_map_into(String::into_bytes).as_slice(), b"abc");
+    }
+
+    #[test]
+    fn top_k_returns_largest_descending() {
+        let v = vec_of(&[5, 1, 8, 3, 9, 2]);
+        assert_eq!(v.top_k(3).as_slice(), [9, 8, 5]);
+        assert_eq!(v.top_k(10).as_slice(), [9, 8, 5, 3, 2, 1]);
+        assert!(v.top_k(0).is_empty());
+    }
+
+    #[test]
+    fn is_reverse_of_compares_reversed() {
+        let v = vec_of(&[1, 2, 3]);
+        assert!(v.is_reverse_of(&[3, 2, 1]));
+        assert!(!v.is_reverse_of(&[3, 1, 2]));
+        assert!(!v.is_reverse_of(&[3, 2]));
+    }
+
+    #[test]
+    fn expand_with_default_stuffs_defaults() {
+        let v = vec_of(&[1, 2]);
+        assert_eq!(v.expand_with_default(2).as_slice(), [1, 0, 2, 0]);
+        assert_eq!(v.expand_with_default(1).as_slice(), [1, 2]);
+    }
+
+    #[test]
+    fn longest_range_where_finds_longest_run() {
+        let v = vec_of(&[1, -1, 2, 3, 4, -1, 5]);
+        assert_eq!(v.longest_range_where(|&x| x > 0), Some(2..5));
+        assert_eq!(v.longest_range_where(|&x| x < 0), Some(1..2));
+        assert_eq!(v.longest_range_where(|&x| x > 9), None);
+    }
 }